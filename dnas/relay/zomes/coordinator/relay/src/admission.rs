@@ -0,0 +1,149 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::delegation::is_delegation_chain_revoked;
+use crate::error::RelayError;
+use crate::freeze::check_conversation_open;
+
+/// Outcome of asking the signer of a membrane proof to admit its agent
+#[derive(Serialize, Deserialize, Debug)]
+pub enum AdmitMemberResult {
+    Admitted(ActionHash),
+    // The signer will not admit the agent, for the given reason
+    Refused(String),
+}
+
+/// Admit the calling agent with the membrane proof they joined with, called remotely by `request_admission`
+#[hdk_extern]
+pub fn admit_member(membrane_proof: SerializedBytes) -> ExternResult<AdmitMemberResult> {
+    let agent = call_info()?.provenance;
+    let membrane_proof_kind = MembraneProofKind::decode(MembraneProof::new(membrane_proof.clone()))?;
+    let admissions = query(
+        ChainQueryFilter::new()
+            .entry_type(UnitEntryTypes::Admission.try_into()?)
+            .include_entries(true),
+    )?;
+    let mut uses: u32 = 0;
+    for record in admissions {
+        let Some(admission) = record
+            .entry()
+            .to_app_option::<Admission>()
+            .map_err(|e| wasm_error!(e))? else {
+            continue;
+        };
+        if admission.agent == agent {
+            return Ok(AdmitMemberResult::Admitted(record.action_address().clone()));
+        }
+        if let (
+            MembraneProofKind::InviteToken(envelope),
+            MembraneProofKind::InviteToken(previous_envelope),
+        ) = (
+            &membrane_proof_kind,
+            MembraneProofKind::decode(MembraneProof::new(admission.membrane_proof))?,
+        ) {
+            if previous_envelope.token.key == envelope.token.key {
+                uses += 1;
+            }
+        }
+    }
+
+    let revocations = query(
+        ChainQueryFilter::new()
+            .entry_type(UnitEntryTypes::MembraneProofRevocation.try_into()?)
+            .include_entries(true),
+    )?;
+    for record in revocations {
+        let revocation: Option<MembraneProofRevocation> = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?;
+        if revocation.is_some_and(|r| r.signature == *membrane_proof_kind.signature()) {
+            return Ok(AdmitMemberResult::Refused("Membrane proof was revoked before joining".to_string()));
+        }
+    }
+    if is_delegation_chain_revoked(membrane_proof_kind.delegations(), None)? {
+        return Ok(AdmitMemberResult::Refused("Invite delegation was revoked before joining".to_string()));
+    }
+    if let MembraneProofKind::InviteToken(envelope) = &membrane_proof_kind {
+        if uses >= envelope.token.max_uses {
+            return Ok(AdmitMemberResult::Refused("Invite token has reached its maximum uses".to_string()));
+        }
+    }
+
+    let admission_hash = create_entry(&EntryTypes::Admission(Admission {
+        agent,
+        membrane_proof,
+        status: check_conversation_open()?,
+    }))?;
+    Ok(AdmitMemberResult::Admitted(admission_hash))
+}
+
+/// Ask the signer of my membrane proof to admit me, and record my Membership once they do.
+///
+/// Returns whether I am a member, which is false while the signer cannot be reached or their Admission
+/// has not been published yet.
+#[hdk_extern]
+pub fn request_admission() -> ExternResult<bool> {
+    let Some(props) = dna_properties()? else {
+        return Ok(true);
+    };
+    let me = agent_info()?.agent_initial_pubkey;
+    if props.privacy == Privacy::Public || me == props.progenitor {
+        return Ok(true);
+    }
+    let memberships = query(
+        ChainQueryFilter::new().entry_type(UnitEntryTypes::Membership.try_into()?),
+    )?;
+    if !memberships.is_empty() {
+        return Ok(true);
+    }
+
+    let Some(membrane_proof) = query(
+        ChainQueryFilter::new().action_type(ActionType::AgentValidationPkg),
+    )?
+        .into_iter()
+        .find_map(|record| match record.action() {
+            Action::AgentValidationPkg(AgentValidationPkg { membrane_proof, .. }) => {
+                membrane_proof.clone()
+            }
+            _ => None,
+        }) else {
+        return Err(RelayError::NotFound {
+            kind: "MembraneProof".to_string(),
+            hash: None,
+        }
+        .into());
+    };
+
+    // The proof was signed by the last delegate of its delegation chain, or by the owner it was signed under
+    let membrane_proof_kind = MembraneProofKind::decode(membrane_proof.clone())?;
+    let signer = match membrane_proof_kind.delegations().last() {
+        Some(delegation) => delegation.data.delegate.clone(),
+        None => membrane_proof_kind
+            .handovers()
+            .last()
+            .map_or(props.progenitor, |handover| handover.data.successor.clone()),
+    };
+    let response = call_remote(
+        signer,
+        zome_info()?.name,
+        "admit_member".into(),
+        None,
+        (*membrane_proof).clone(),
+    )?;
+    let ZomeCallResponse::Ok(result) = response else {
+        return Ok(false);
+    };
+    let admission = match result.decode().map_err(|e| wasm_error!(e))? {
+        AdmitMemberResult::Admitted(admission) => admission,
+        AdmitMemberResult::Refused(reason) => {
+            return Err(RelayError::AdmissionRefused { reason }.into());
+        }
+    };
+    // Validating the Membership needs the Admission, which may not have been published yet
+    if get(admission.clone(), GetOptions::default())?.is_none() {
+        return Ok(false);
+    }
+    create_entry(&EntryTypes::Membership(Membership { admission }))?;
+    Ok(true)
+}
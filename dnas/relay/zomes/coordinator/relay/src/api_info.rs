@@ -0,0 +1,48 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::remote_signal::REMOTE_SIGNAL_PROTOCOL_VERSION;
+
+// Features clients can detect with `get_api_info`, add one for every new group of zome functions
+pub const FEATURES: &[&str] = &[
+    "roles",
+    "membrane_proof_validity",
+    "membrane_proof_revocation",
+    "member_admission",
+    "invite_tokens",
+    "invite_delegation",
+    "invite_delegation_revocation",
+    "ownership_handover",
+    "ownership_reclaim",
+    "slow_mode",
+    "conversation_freeze",
+    "typed_errors",
+    "entry_schema_versions",
+    "api_info",
+    "replies",
+    "reactions",
+    "read_receipts",
+    "delivery_acks",
+    "typing_indicators",
+    "remote_signal_envelope",
+];
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ApiInfo {
+    pub coordinator_version: String,
+    pub integrity_schema_version: u32,
+    pub remote_signal_protocol_version: u32,
+    pub features: Vec<String>,
+}
+
+/// Describe this coordinator, so clients can tell which version runs in each cell once coordinators are updated
+#[hdk_extern]
+pub fn get_api_info() -> ExternResult<ApiInfo> {
+    Ok(ApiInfo {
+        // The version in the relay crate manifest, bumped with every coordinator release
+        coordinator_version: env!("CARGO_PKG_VERSION").to_string(),
+        integrity_schema_version: INTEGRITY_SCHEMA_VERSION,
+        remote_signal_protocol_version: REMOTE_SIGNAL_PROTOCOL_VERSION,
+        features: FEATURES.iter().map(|feature| feature.to_string()).collect(),
+    })
+}
@@ -1,7 +1,19 @@
 use hdk::prelude::*;
 use relay_integrity::*;
+
+use crate::error::RelayError;
+
+use crate::freeze::check_conversation_open;
+use crate::get_my_role;
+use crate::schema::decode_versioned;
+
 #[hdk_extern]
-pub fn set_config(config: Config) -> ExternResult<()> {
+pub fn set_config(mut config: Config) -> ExternResult<()> {
+    if !get_my_role(())?.can_configure() {
+        return Err(RelayError::unauthorized("set_config").into());
+    }
+    config.status = check_conversation_open()?;
+    config.schema_version = Config::SCHEMA_VERSION;
     let config_hash = create_entry(&EntryTypes::Config(config.clone()))?;
     let path = Path::from("config");
     let _link = create_link(
@@ -13,9 +25,10 @@ pub fn set_config(config: Config) -> ExternResult<()> {
     Ok(())
 }
 
+/// Get the latest Config, upgraded to the current schema version
 #[hdk_extern]
 pub fn get_config(
-) -> ExternResult<Option<Record>> {
+) -> ExternResult<Option<Config>> {
     let path = Path::from("config");
     let links = get_links(
         GetLinksInputBuilder::try_new(
@@ -32,12 +45,12 @@ pub fn get_config(
                 .clone()
                 .into_action_hash()
                 .ok_or(
-                    wasm_error!(
-                        WasmErrorInner::Guest("No action hash associated with link"
-                        .to_string())
-                    ),
+                    RelayError::invalid_link_target("ConfigUpdates"),
                 )?;
-        get(latest_config_hash, GetOptions::default())
+        match get(latest_config_hash, GetOptions::default())? {
+            Some(record) => decode_versioned(&record),
+            None => Ok(None),
+        }
     } else {
         Ok(None)
     }
@@ -1,8 +1,12 @@
 use hdk::prelude::*;
 use relay_integrity::*;
 
+use crate::error::RelayError;
+use crate::schema::decode_versioned;
+
 #[hdk_extern]
-pub fn create_contact(contact: Contact) -> ExternResult<Record> {
+pub fn create_contact(mut contact: Contact) -> ExternResult<Record> {
+    contact.schema_version = Contact::SCHEMA_VERSION;
     let contact_hash = create_entry(&EntryTypes::Contact(contact.clone()))?;
     create_link(
         contact.public_key.clone(),
@@ -12,10 +16,7 @@ pub fn create_contact(contact: Contact) -> ExternResult<Record> {
     )?;
     let record = get(contact_hash.clone(), GetOptions::default())?
         .ok_or(
-            wasm_error!(
-                WasmErrorInner::Guest("Could not find the newly created Contact"
-                .to_string())
-            ),
+            RelayError::not_created("Contact"),
         )?;
     let path = Path::from("all_contacts");
     create_link(
@@ -47,10 +48,7 @@ pub fn get_latest_contact(
                 .clone()
                 .into_action_hash()
                 .ok_or(
-                    wasm_error!(
-                        WasmErrorInner::Guest("No action hash associated with link"
-                        .to_string())
-                    ),
+                    RelayError::invalid_link_target("ContactUpdates"),
                 )?
         }
         None => original_contact_hash.clone(),
@@ -60,7 +58,7 @@ pub fn get_latest_contact(
             Ok(Some(ContactRecord {
                 original_action: original_contact_hash,
                 signed_action: record.signed_action().clone(),
-                contact: record.entry().to_app_option().map_err(|e| wasm_error!(e))?,
+                contact: decode_versioned(&record)?,
             }))
         },
         None => Ok(None)
@@ -77,11 +75,7 @@ pub fn get_original_contact(
     match details {
         Details::Record(details) => Ok(Some(details.record)),
         _ => {
-            Err(
-                wasm_error!(
-                    WasmErrorInner::Guest("Malformed get details response".to_string())
-                ),
-            )
+            Err(RelayError::malformed("Contact", "expected a record").into())
         }
     }
 }
@@ -109,10 +103,7 @@ pub fn get_all_revisions_for_contact(
                     .target
                     .into_action_hash()
                     .ok_or(
-                        wasm_error!(
-                            WasmErrorInner::Guest("No action hash associated with link"
-                            .to_string())
-                        ),
+                        RelayError::invalid_link_target("ContactUpdates"),
                     )?
                     .into(),
                 GetOptions::default(),
@@ -156,7 +147,8 @@ pub struct UpdateContactInput {
 }
 
 #[hdk_extern]
-pub fn update_contact(input: UpdateContactInput) -> ExternResult<Record> {
+pub fn update_contact(mut input: UpdateContactInput) -> ExternResult<Record> {
+    input.updated_contact.schema_version = Contact::SCHEMA_VERSION;
     let updated_contact_hash = update_entry(
         input.previous_contact_hash.clone(),
         &input.updated_contact,
@@ -169,10 +161,7 @@ pub fn update_contact(input: UpdateContactInput) -> ExternResult<Record> {
     )?;
     let record = get(updated_contact_hash.clone(), GetOptions::default())?
         .ok_or(
-            wasm_error!(
-                WasmErrorInner::Guest("Could not find the newly updated Contact"
-                .to_string())
-            ),
+            RelayError::not_created("Contact"),
         )?;
     Ok(record)
 }
@@ -180,14 +169,12 @@ pub fn update_contact(input: UpdateContactInput) -> ExternResult<Record> {
 #[hdk_extern]
 pub fn delete_contact(original_contact_hash: ActionHash) -> ExternResult<ActionHash> {
     let details = get_details(original_contact_hash.clone(), GetOptions::default())?
-        .ok_or(wasm_error!(WasmErrorInner::Guest("Contact not found".to_string())))?;
+        .ok_or(RelayError::not_found("Contact", &original_contact_hash))?;
     let record = match details {
         Details::Record(details) => Ok(details.record),
         _ => {
             Err(
-                wasm_error!(
-                    WasmErrorInner::Guest("Malformed get details response".to_string())
-                ),
+                RelayError::malformed("Contact", "expected a record"),
             )
         }
     }?;
@@ -195,7 +182,7 @@ pub fn delete_contact(original_contact_hash: ActionHash) -> ExternResult<ActionH
         .entry()
         .as_option()
         .ok_or(
-            wasm_error!(WasmErrorInner::Guest("Contact record has no entry".to_string())),
+            RelayError::malformed("Contact", "record has no entry"),
         )?;
     let contact = <Contact>::try_from(entry)?;
     let links = get_links(
@@ -236,7 +223,7 @@ pub fn get_all_deletes_for_contact(
     };
     match details {
         Details::Entry(_) => {
-            Err(wasm_error!(WasmErrorInner::Guest("Malformed details".into())))
+            Err(RelayError::malformed("Contact", "expected a record").into())
         }
         Details::Record(record_details) => Ok(Some(record_details.deletes)),
     }
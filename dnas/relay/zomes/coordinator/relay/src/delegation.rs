@@ -0,0 +1,162 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+
+use crate::freeze::check_conversation_open;
+use crate::ownership::get_my_ownership_handovers;
+
+/// Grant `delegate` the authority to invite members, extending the delegation chain that gave it to me
+#[hdk_extern]
+pub fn grant_invite_authority(delegate: AgentPubKey) -> ExternResult<Record> {
+    let status = check_conversation_open()?;
+    let Some(mut invite_delegation) = get_my_invite_delegations()? else {
+        return Err(RelayError::unauthorized("grant_invite_authority").into());
+    };
+    let me = agent_info()?.agent_latest_pubkey;
+    let data = InviteDelegationData {
+        conversation_id: dna_info()?.modifiers.network_seed,
+        delegate: delegate.clone(),
+    };
+    invite_delegation.chain.push(InviteDelegationEnvelope {
+        signature: sign(me, data.clone())?,
+        data,
+    });
+    invite_delegation.status = status;
+    let invite_delegation_hash = create_entry(
+        &EntryTypes::InviteDelegation(invite_delegation),
+    )?;
+    create_link(
+        delegate,
+        invite_delegation_hash.clone(),
+        LinkTypes::AgentToInviteDelegations,
+        (),
+    )?;
+    let record = get(invite_delegation_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("InviteDelegation"),
+        )?;
+    Ok(record)
+}
+
+/// Get the shortest valid delegation chain granting invite authority to `agent`
+#[hdk_extern]
+pub fn get_invite_delegation_for_agent(
+    agent: AgentPubKey,
+) -> ExternResult<Option<InviteDelegation>> {
+    let Some(props) = dna_properties()? else {
+        return Ok(None);
+    };
+    let conversation_id = dna_info()?.modifiers.network_seed;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(agent.clone(), LinkTypes::AgentToInviteDelegations)?
+            .build(),
+    )?;
+    let mut result: Option<InviteDelegation> = None;
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        let Some(record) = get(hash, GetOptions::default())? else {
+            continue;
+        };
+        let Some(invite_delegation) = record
+            .entry()
+            .to_app_option::<InviteDelegation>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        if verify_invite_authority(
+            &props,
+            &conversation_id,
+            &invite_delegation.handovers,
+            &invite_delegation.chain,
+        )? != Some(agent.clone())
+        {
+            continue;
+        }
+        if is_delegation_chain_revoked(&invite_delegation.chain, None)? {
+            continue;
+        }
+        if !result
+            .as_ref()
+            .is_some_and(|r| r.chain.len() <= invite_delegation.chain.len())
+        {
+            result = Some(invite_delegation);
+        }
+    }
+    Ok(result)
+}
+
+/// The handover and delegation chains to include in the proofs I sign: no delegations for the owner,
+/// None if I have not been granted invite authority
+pub fn get_my_invite_delegations() -> ExternResult<Option<InviteDelegation>> {
+    if let Some(handovers) = get_my_ownership_handovers()? {
+        return Ok(Some(InviteDelegation {
+            handovers,
+            chain: Vec::new(),
+            status: None,
+        }));
+    }
+    let me = agent_info()?.agent_initial_pubkey;
+    get_invite_delegation_for_agent(me)
+}
+
+/// Revoke a delegation I signed, or any delegation as the owner
+#[hdk_extern]
+pub fn revoke_invite_delegation(delegation: InviteDelegationEnvelope) -> ExternResult<Record> {
+    let revocation = InviteDelegationRevocation { delegation };
+    let revocation_hash = create_entry(
+        &EntryTypes::InviteDelegationRevocation(revocation.clone()),
+    )?;
+    create_link(
+        revocation.delegation.data.delegate.clone(),
+        revocation_hash.clone(),
+        LinkTypes::AgentToInviteDelegationRevocations,
+        (),
+    )?;
+    let record = get(revocation_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("InviteDelegationRevocation"),
+        )?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_invite_delegation_revocations_for_agent(
+    agent: AgentPubKey,
+) -> ExternResult<Vec<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToInviteDelegationRevocations)?
+            .build(),
+    )?;
+    let mut records: Vec<Record> = Vec::new();
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        if let Some(record) = get(hash, GetOptions::default())? {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+/// Whether any delegation in `chain` was revoked, before `before` when set
+pub fn is_delegation_chain_revoked(
+    chain: &[InviteDelegationEnvelope],
+    before: Option<Timestamp>,
+) -> ExternResult<bool> {
+    for envelope in chain {
+        for record in get_invite_delegation_revocations_for_agent(envelope.data.delegate.clone())? {
+            if before.is_some_and(|before| record.action().timestamp() >= before) {
+                continue;
+            }
+            let revocation: Option<InviteDelegationRevocation> = record
+                .entry()
+                .to_app_option()
+                .map_err(|e| wasm_error!(e))?;
+            if revocation.is_some_and(|r| r.delegation.signature == envelope.signature) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
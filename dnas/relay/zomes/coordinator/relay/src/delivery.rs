@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+use crate::remote_signal::{send_relay_signal, RemoteSignal};
+use crate::schema::decode_versioned;
+
+// Delay before the first retry, doubled after each attempt
+pub const RETRY_BASE_MS: i64 = 1000 * 30; // 30 seconds
+// Including the first send, after which a recipient who has not acknowledged the message is marked as failed
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 6;
+// How often the outbox is checked for messages to retry while any are pending
+const RETRY_SCHEDULE: &str = "*/30 * * * * * *";
+const RETRY_INTERVAL_MS: i64 = 1000 * 30;
+
+/// Sent back by recipients when they receive a message
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeliveryAck {
+    pub delivered: ActionHash,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum DeliveryStatus {
+    Sent,
+    Delivered,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecipientDeliveryStatus {
+    pub agent: AgentPubKey,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+}
+
+/// A message in my outbox, with when each recipient acknowledged it
+struct OutboxItem {
+    delivery: Delivery,
+    delivered: HashMap<AgentPubKey, Timestamp>,
+}
+
+/// When attempt `attempt` is made, counting the first send as attempt 1
+fn attempt_at(delivery: &Delivery, attempt: u32) -> ExternResult<Timestamp> {
+    let delay_ms = RETRY_BASE_MS * ((1 << attempt.saturating_sub(1).min(16)) - 1);
+    (delivery.sent_at + std::time::Duration::from_millis(delay_ms as u64))
+        .map_err(|e| RelayError::malformed("Delivery", &e.to_string()).into())
+}
+
+/// Number of attempts made by `now`
+fn attempts_by(delivery: &Delivery, now: Timestamp) -> ExternResult<u32> {
+    let mut attempts = 1;
+    while attempts < MAX_DELIVERY_ATTEMPTS && attempt_at(delivery, attempts + 1)? <= now {
+        attempts += 1;
+    }
+    Ok(attempts)
+}
+
+/// Whether unacknowledged recipients will still be retried after `now`
+fn is_retrying(item: &OutboxItem, now: Timestamp) -> ExternResult<bool> {
+    Ok(item
+        .delivery
+        .recipients
+        .iter()
+        .any(|agent| !item.delivered.contains_key(agent))
+        && attempt_at(&item.delivery, MAX_DELIVERY_ATTEMPTS + 1)? > now)
+}
+
+fn recipient_status(item: &OutboxItem, agent: &AgentPubKey, now: Timestamp) -> ExternResult<RecipientDeliveryStatus> {
+    let (status, attempts) = match item.delivered.get(agent) {
+        Some(delivered_at) => (DeliveryStatus::Delivered, attempts_by(&item.delivery, *delivered_at)?),
+        None if !is_retrying(item, now)? => (DeliveryStatus::Failed, MAX_DELIVERY_ATTEMPTS),
+        None => (DeliveryStatus::Sent, attempts_by(&item.delivery, now)?),
+    };
+    Ok(RecipientDeliveryStatus {
+        agent: agent.clone(),
+        status,
+        attempts,
+    })
+}
+
+/// When deliveries still being retried at `now` were sent at the earliest
+fn retry_window_start(now: Timestamp) -> ExternResult<Timestamp> {
+    let window_ms = RETRY_BASE_MS * ((1 << MAX_DELIVERY_ATTEMPTS) - 1);
+    (now - std::time::Duration::from_millis(window_ms as u64))
+        .map_err(|e| RelayError::malformed("Delivery", &e.to_string()).into())
+}
+
+/// Entries of `entry_type` in my source chain, from the action at `from_seq` on
+fn query_my_entries<T>(entry_type: UnitEntryTypes, from_seq: Option<u32>) -> ExternResult<Vec<T>>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let mut filter = ChainQueryFilter::new()
+        .entry_type(entry_type.try_into()?)
+        .include_entries(true);
+    if let Some(from_seq) = from_seq {
+        let chain_head = agent_info()?.chain_head.1;
+        filter = filter.sequence_range(ChainQueryFilterRange::ActionSeqRange(from_seq, chain_head));
+    }
+    let mut entries: Vec<T> = Vec::new();
+    for record in query(filter)? {
+        let entry: Option<T> = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+        entries.extend(entry);
+    }
+    Ok(entries)
+}
+
+/// The deliveries in my source chain with their confirmations, keyed by message.
+///
+/// With `since`, only deliveries sent from then on are loaded, so the outbox is not scanned from genesis.
+fn get_my_deliveries(since: Option<Timestamp>) -> ExternResult<HashMap<ActionHash, OutboxItem>> {
+    let from_seq = match since {
+        Some(since) => {
+            let actions = query(ChainQueryFilter::new().entry_type(UnitEntryTypes::Delivery.try_into()?))?;
+            match actions.iter().find(|record| record.action().timestamp() >= since) {
+                Some(record) => Some(record.action().action_seq()),
+                None => return Ok(HashMap::new()),
+            }
+        }
+        None => None,
+    };
+    let mut deliveries: HashMap<ActionHash, OutboxItem> = HashMap::new();
+    for delivery in query_my_entries::<Delivery>(UnitEntryTypes::Delivery, from_seq)? {
+        deliveries.insert(
+            delivery.message.clone(),
+            OutboxItem {
+                delivery,
+                delivered: HashMap::new(),
+            },
+        );
+    }
+    for confirmation in query_my_entries::<DeliveryConfirmation>(UnitEntryTypes::DeliveryConfirmation, from_seq)? {
+        if let Some(item) = deliveries.get_mut(&confirmation.message) {
+            item.delivered.insert(confirmation.agent, confirmation.delivered_at);
+        }
+    }
+    Ok(deliveries)
+}
+
+fn send_message(message_hash: &ActionHash, agents: Vec<AgentPubKey>) -> ExternResult<()> {
+    let record = get(message_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_found("Message", message_hash),
+        )?;
+    let message: Option<Message> = decode_versioned(&record)?;
+    if let Err(err) = send_relay_signal(
+        RemoteSignal::Message(MessageRecord {
+            message,
+            original_action: message_hash.clone(),
+            signed_action: record.signed_action().clone(),
+        }),
+        agents,
+    ) {
+        debug!("Error sending message {:?}: {:?}", message_hash, err);
+    }
+    Ok(())
+}
+
+/// Send a new message to `agents`, and add it to the outbox until they acknowledge it
+pub fn send_message_to_agents(message_hash: ActionHash, agents: Vec<AgentPubKey>) -> ExternResult<()> {
+    if agents.is_empty() {
+        return Ok(());
+    }
+    send_message(&message_hash, agents.clone())?;
+    create_entry(&EntryTypes::Delivery(Delivery {
+        message: message_hash,
+        sent_at: sys_time()?,
+        recipients: agents,
+    }))?;
+    schedule("retry_deliveries")?;
+    Ok(())
+}
+
+/// Resend messages to the recipients who have not acknowledged them, backing off after each attempt,
+/// until no message is waiting for a retry
+#[hdk_extern(infallible)]
+fn retry_deliveries(_: Option<Schedule>) -> Option<Schedule> {
+    match retry_pending_deliveries() {
+        Ok(false) => None,
+        Ok(true) => Some(Schedule::Persisted(RETRY_SCHEDULE.to_string())),
+        Err(err) => {
+            error!("Error retrying deliveries: {:?}", err);
+            Some(Schedule::Persisted(RETRY_SCHEDULE.to_string()))
+        }
+    }
+}
+
+/// Resend the messages due for a retry since the previous run, returning whether any are still pending
+fn retry_pending_deliveries() -> ExternResult<bool> {
+    let now = sys_time()?;
+    let previous_run = (now - std::time::Duration::from_millis(RETRY_INTERVAL_MS as u64))
+        .map_err(|e| RelayError::malformed("Delivery", &e.to_string()))?;
+    let mut is_pending = false;
+    for (message_hash, item) in get_my_deliveries(Some(retry_window_start(now)?))? {
+        if !is_retrying(&item, now)? {
+            continue;
+        }
+        is_pending = true;
+        let attempts = attempts_by(&item.delivery, now)?;
+        if attempts == 1 || attempt_at(&item.delivery, attempts)? <= previous_run {
+            continue;
+        }
+        let agents: Vec<AgentPubKey> = item
+            .delivery
+            .recipients
+            .into_iter()
+            .filter(|agent| !item.delivered.contains_key(agent))
+            .collect();
+        send_message(&message_hash, agents)?;
+    }
+    Ok(is_pending)
+}
+
+/// Record that `from` received `message_hash`, returning whether it was waiting in the outbox
+pub fn record_delivery_ack(from: AgentPubKey, message_hash: ActionHash) -> ExternResult<bool> {
+    let now = sys_time()?;
+    let Some(item) = get_my_deliveries(Some(retry_window_start(now)?))?.remove(&message_hash) else {
+        return Ok(false);
+    };
+    if !item.delivery.recipients.contains(&from) || item.delivered.contains_key(&from) {
+        return Ok(false);
+    }
+    create_entry(&EntryTypes::DeliveryConfirmation(DeliveryConfirmation {
+        message: message_hash,
+        agent: from,
+        delivered_at: now,
+    }))?;
+    Ok(true)
+}
+
+/// The agents a message I sent was delivered to, or is still being retried for
+pub fn get_delivery_recipients(message_hash: &ActionHash) -> ExternResult<Vec<AgentPubKey>> {
+    Ok(query_my_entries::<Delivery>(UnitEntryTypes::Delivery, None)?
+        .into_iter()
+        .find(|delivery| delivery.message == *message_hash)
+        .map(|delivery| delivery.recipients)
+        .unwrap_or_default())
+}
+
+/// Get whether each recipient of a message I sent has acknowledged it, and how many times it was sent to them
+#[hdk_extern]
+pub fn get_delivery_status(message_hash: ActionHash) -> ExternResult<Vec<RecipientDeliveryStatus>> {
+    let Some(item) = get_my_deliveries(None)?.remove(&message_hash) else {
+        return Ok(Vec::new());
+    };
+    let now = sys_time()?;
+    item.delivery
+        .recipients
+        .iter()
+        .map(|agent| recipient_status(&item, agent, now))
+        .collect()
+}
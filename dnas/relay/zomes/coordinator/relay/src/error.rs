@@ -0,0 +1,86 @@
+use hdk::prelude::*;
+
+/// Errors returned by the relay zome functions.
+///
+/// They are serialized as JSON into the guest error message, tagged with a stable `code`,
+/// e.g. `{"code":"not_found","kind":"Message","hash":"uhCkk..."}`, so clients can branch on the code.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum RelayError {
+    // A record could not be fetched, `hash` is None for records we just created
+    NotFound {
+        kind: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hash: Option<String>,
+    },
+    // A record was fetched but does not have the expected shape
+    MalformedRecord { kind: String, reason: String },
+    // A link points to something other than an action
+    InvalidLinkTarget { link_type: String },
+    // The caller's role does not allow the action
+    Unauthorized { action: String },
+    // A membrane proof role which is not one of ROLE_MEMBER, ROLE_ADMIN or ROLE_GUEST
+    UnknownRole { role: u32 },
+    // The conversation reached its end date or was frozen
+    ConversationClosed { closed_at: Timestamp },
+    // An ownership handover was already accepted or cancelled
+    HandoverNotPending { hash: String },
+    // A handover cannot be reclaimed until it expired and its successor was active since
+    HandoverNotExpired { hash: String },
+    // The signer of the caller's membrane proof refused to admit them
+    AdmissionRefused { reason: String },
+}
+
+impl RelayError {
+    pub fn not_found(kind: &str, hash: &impl ToString) -> Self {
+        RelayError::NotFound {
+            kind: kind.to_string(),
+            hash: Some(hash.to_string()),
+        }
+    }
+
+    pub fn not_created(kind: &str) -> Self {
+        RelayError::NotFound {
+            kind: kind.to_string(),
+            hash: None,
+        }
+    }
+
+    pub fn malformed(kind: &str, reason: &str) -> Self {
+        RelayError::MalformedRecord {
+            kind: kind.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_link_target(link_type: &str) -> Self {
+        RelayError::InvalidLinkTarget {
+            link_type: link_type.to_string(),
+        }
+    }
+
+    pub fn unauthorized(action: &str) -> Self {
+        RelayError::Unauthorized {
+            action: action.to_string(),
+        }
+    }
+
+    pub fn handover_not_pending(hash: &impl ToString) -> Self {
+        RelayError::HandoverNotPending {
+            hash: hash.to_string(),
+        }
+    }
+
+    pub fn handover_not_expired(hash: &impl ToString) -> Self {
+        RelayError::HandoverNotExpired {
+            hash: hash.to_string(),
+        }
+    }
+}
+
+impl From<RelayError> for WasmError {
+    fn from(error: RelayError) -> Self {
+        let message = serde_json::to_string(&error).unwrap_or_else(|_| format!("{:?}", error));
+        wasm_error!(WasmErrorInner::Guest(message))
+    }
+}
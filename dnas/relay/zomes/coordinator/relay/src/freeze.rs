@@ -0,0 +1,144 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+use crate::ownership::get_my_ownership_handovers;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FreezeConversationInput {
+    pub reason: String,
+}
+
+/// Record the status the progenitor's first call starts the conversation with
+pub fn create_conversation_status() -> ExternResult<()> {
+    let conversation_status_hash = create_entry(
+        &EntryTypes::ConversationStatus(ConversationStatus { frozen: None }),
+    )?;
+    create_link(
+        Path::from(STATUS_PATH).path_entry_hash()?,
+        conversation_status_hash,
+        LinkTypes::ConversationStatusUpdates,
+        (),
+    )?;
+    Ok(())
+}
+
+#[hdk_extern]
+pub fn freeze_conversation(input: FreezeConversationInput) -> ExternResult<Record> {
+    if get_my_ownership_handovers()?.is_none() {
+        return Err(RelayError::unauthorized("freeze_conversation").into());
+    }
+    let status_hash = get_conversation_status_hash()?;
+    let conversation_status_hash = update_entry(
+        status_hash,
+        &EntryTypes::ConversationStatus(ConversationStatus {
+            frozen: Some(input.reason),
+        }),
+    )?;
+    create_link(
+        Path::from(STATUS_PATH).path_entry_hash()?,
+        conversation_status_hash.clone(),
+        LinkTypes::ConversationStatusUpdates,
+        (),
+    )?;
+    let record = get(conversation_status_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("ConversationStatus"),
+        )?;
+    Ok(record)
+}
+
+/// Get the latest status of the conversation, which is frozen once the owner froze it
+#[hdk_extern]
+pub fn get_conversation_status() -> ExternResult<Option<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+                Path::from(STATUS_PATH).path_entry_hash()?,
+                LinkTypes::ConversationStatusUpdates,
+            )?
+            .build(),
+    )?;
+    let is_frozen = |record: &Record| {
+        matches!(
+            record.entry().to_app_option::<ConversationStatus>(),
+            Ok(Some(ConversationStatus { frozen: Some(_) }))
+        )
+    };
+    let mut result: Option<Record> = None;
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        let Some(record) = get(hash, GetOptions::default())? else {
+            continue;
+        };
+        // A status can only be frozen once, so the frozen one is the latest whatever its timestamp
+        if !result.as_ref().is_some_and(|r| {
+            is_frozen(r)
+                || (!is_frozen(&record) && r.action().timestamp() >= record.action().timestamp())
+        }) {
+            result = Some(record);
+        }
+    }
+    Ok(result)
+}
+
+/// Get the status of the conversation if the owner froze it
+#[hdk_extern]
+pub fn get_conversation_freeze() -> ExternResult<Option<Record>> {
+    let Some(record) = get_conversation_status(())? else {
+        return Ok(None);
+    };
+    let conversation_status: Option<ConversationStatus> = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?;
+    Ok(conversation_status.and_then(|s| s.frozen).map(|_| record))
+}
+
+/// The status that actions must cite, which may not have reached me yet right after joining
+fn get_conversation_status_hash() -> ExternResult<ActionHash> {
+    get_conversation_status(())?
+        .map(|record| record.action_address().clone())
+        .ok_or(
+            RelayError::NotFound {
+                kind: "ConversationStatus".to_string(),
+                hash: None,
+            }
+            .into(),
+        )
+}
+
+/// When the conversation became read-only, by reaching its end date or being frozen
+#[hdk_extern]
+pub fn get_conversation_closed_at() -> ExternResult<Option<Timestamp>> {
+    let ends_at = dna_properties()?
+        .and_then(|props| props.ends_at_ms)
+        .map(|ends_at_ms| Timestamp::from_micros(ends_at_ms * 1000));
+    let frozen_at = get_conversation_freeze(())?.map(|record| record.action().timestamp());
+    Ok(ends_at.into_iter().chain(frozen_at).min())
+}
+
+/// Refuse to change a closed conversation, returning the status to cite otherwise
+pub fn check_conversation_open() -> ExternResult<Option<ActionHash>> {
+    match get_conversation_closed_at(())? {
+        Some(closed_at) if closed_at <= sys_time()? => {
+            return Err(RelayError::ConversationClosed { closed_at }.into());
+        }
+        _ => {}
+    }
+    if dna_properties()?.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(get_conversation_status_hash()?))
+}
+
+/// Hide messages timestamped after the conversation was frozen, which agents citing a stale status could create
+pub fn filter_frozen_messages(records: Vec<MessageRecord>) -> ExternResult<Vec<MessageRecord>> {
+    let Some(freeze) = get_conversation_freeze(())? else {
+        return Ok(records);
+    };
+    let frozen_at = freeze.action().timestamp();
+    Ok(records
+        .into_iter()
+        .filter(|record| record.signed_action.action().timestamp() < frozen_at)
+        .collect())
+}
@@ -0,0 +1,48 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+
+use crate::delegation::get_my_invite_delegations;
+use crate::freeze::check_conversation_open;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GenerateInviteTokenInput {
+    // Raw ed25519 public key of the secret key shared with the token
+    pub key: Vec<u8>,
+    pub max_uses: u32,
+    pub expires_at: Timestamp,
+    pub as_role: u32,
+}
+
+#[hdk_extern]
+pub fn generate_invite_token(input: GenerateInviteTokenInput) -> ExternResult<SerializedBytes> {
+    check_conversation_open()?;
+    let Some(invite_delegation) = get_my_invite_delegations()? else {
+        return Err(RelayError::unauthorized("generate_invite_token").into());
+    };
+    if Role::from_membrane_proof_role(input.as_role).is_none() {
+        return Err(RelayError::UnknownRole { role: input.as_role }.into());
+    }
+    if !invite_delegation.chain.is_empty() && !can_delegate_role(input.as_role) {
+        return Err(RelayError::unauthorized("generate_invite_token").into());
+    }
+    let me = agent_info()?.agent_latest_pubkey;
+    let token = InviteToken {
+        conversation_id: dna_info()?.modifiers.network_seed,
+        key: input.key,
+        max_uses: input.max_uses,
+        expires_at: input.expires_at,
+        as_role: input.as_role,
+        issued_after: Some(agent_info()?.chain_head.0),
+    };
+    let result = InviteTokenEnvelope {
+        signature: sign(me, token.clone())?,
+        token,
+        agent_signature: None,
+        handovers: invite_delegation.handovers,
+        delegations: invite_delegation.chain,
+    };
+    let proof = SerializedBytes::try_from(result).map_err(|e| wasm_error!(e))?;
+    Ok(proof)
+}
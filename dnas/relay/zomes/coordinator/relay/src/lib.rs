@@ -1,32 +1,151 @@
+pub mod error;
 pub mod contact;
 pub mod message;
 pub mod config;
 pub mod ping;
+pub mod revocation;
+pub mod admission;
+pub mod invite_token;
+pub mod delegation;
+pub mod ownership;
+pub mod freeze;
+pub mod schema;
+pub mod api_info;
+pub mod reply;
+pub mod reaction;
+pub mod read;
+pub mod delivery;
+pub mod typing;
+pub mod remote_signal;
 use hdk::prelude::*;
 use relay_integrity::*;
 
+use crate::error::RelayError;
+use crate::delivery::DeliveryAck;
+use crate::remote_signal::{
+    authenticate_remote_signal, send_relay_signal, MessageDeleted, ReceivedSignal, RemoteSignal,
+    REMOTE_SIGNAL_PROTOCOL_VERSION,
+};
+use crate::typing::TypingState;
+
 #[hdk_extern]
-fn recv_remote_signal(message_record: MessageRecord) -> ExternResult<()> {
+fn recv_remote_signal(received: ReceivedSignal) -> ExternResult<()> {
     let info: CallInfo = call_info()?;
-    let message = message_record.message.unwrap();
-    let signal = Signal::Message {
-        action: message_record.signed_action.clone(),
-        message,
-        from: info.provenance,
-    };
-    emit_signal(signal)
+    if received.protocol_version() > REMOTE_SIGNAL_PROTOCOL_VERSION {
+        debug!(
+            "Remote signal from {:?} uses newer protocol version {}",
+            info.provenance,
+            received.protocol_version(),
+        );
+    }
+    let remote_signal = received.into_signal();
+    // Anyone can call recv_remote_signal, so drop signals spoofing another agent or sent by non-members
+    if !authenticate_remote_signal(&info.provenance, &remote_signal)? {
+        debug!("Dropping unauthenticated remote signal from {:?}", info.provenance);
+        return Ok(());
+    }
+    match remote_signal {
+        RemoteSignal::Message(message_record) => {
+            // Acknowledge the message, so the sender stops retrying
+            let _ = send_relay_signal(
+                RemoteSignal::DeliveryAck(DeliveryAck {
+                    delivered: message_record.original_action.clone(),
+                }),
+                vec![info.provenance.clone()],
+            );
+            let message = message_record
+                .message
+                .ok_or(RelayError::malformed("MessageRecord", "signal has no message"))?
+                .upgrade();
+            if let Some(parent) = message.reply_to.clone() {
+                emit_signal(Signal::Reply {
+                    parent,
+                    action: message_record.signed_action.clone(),
+                    message: message.clone(),
+                    from: info.provenance.clone(),
+                })?;
+            }
+            emit_signal(Signal::Message {
+                action: message_record.signed_action,
+                message,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::MessageUpdated(message_record) => {
+            let message = message_record
+                .message
+                .ok_or(RelayError::malformed("MessageRecord", "signal has no message"))?
+                .upgrade();
+            emit_signal(Signal::MessageUpdated {
+                original_action: message_record.original_action,
+                action: message_record.signed_action,
+                message,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::MessageDeleted(MessageDeleted { message, delete }) => {
+            emit_signal(Signal::MessageDeleted {
+                message,
+                action: delete,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::Reaction(update) => {
+            emit_signal(Signal::Reaction {
+                message: update.message,
+                emoji: update.emoji,
+                removed: update.removed,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::ReadReceipt(envelope) => {
+            emit_signal(Signal::ReadReceipt {
+                receipt: envelope.receipt,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::DeliveryAck(ack) => {
+            if delivery::record_delivery_ack(info.provenance.clone(), ack.delivered.clone())? {
+                emit_signal(Signal::Delivered {
+                    message: ack.delivered,
+                    from: info.provenance,
+                })?;
+            }
+            Ok(())
+        }
+        RemoteSignal::Typing(update) => {
+            let Some(expires_at) = typing::accept_typing(&info.provenance, &update)? else {
+                return Ok(());
+            };
+            emit_signal(Signal::Typing {
+                state: update.typing,
+                expires_at,
+                from: info.provenance,
+            })
+        }
+        RemoteSignal::Unknown => {
+            debug!("Ignoring remote signal of unknown kind from {:?}", info.provenance);
+            Ok(())
+        }
+    }
 }
 
 #[hdk_extern]
 pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
     let mut fns = BTreeSet::new();
     fns.insert((zome_info()?.name, "recv_remote_signal".into()));
+    fns.insert((zome_info()?.name, "admit_member".into()));
     let functions = GrantedFunctions::Listed(fns);
+    // Any agent can send remote signals, recv_remote_signal authenticates them
     create_cap_grant(CapGrantEntry {
         tag: "".into(),
         access: CapAccess::Unrestricted,
         functions,
     })?;
+    let me = agent_info()?.agent_initial_pubkey;
+    if dna_properties()?.is_some_and(|props| props.progenitor == me) {
+        freeze::create_conversation_status()?;
+    }
 
     Ok(InitCallbackResult::Pass)
 }
@@ -34,6 +153,25 @@ pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
 #[serde(tag = "type")]
 pub enum Signal {
     Message { action: SignedActionHashed, message: Message, from: AgentPubKey },
+    MessageUpdated {
+        original_action: ActionHash,
+        action: SignedActionHashed,
+        message: Message,
+        from: AgentPubKey,
+    },
+    MessageDeleted { message: ActionHash, action: SignedActionHashed, from: AgentPubKey },
+    // Sent alongside Message for replies, so open threads can update
+    Reply {
+        parent: ActionHash,
+        action: SignedActionHashed,
+        message: Message,
+        from: AgentPubKey,
+    },
+    Reaction { message: ActionHash, emoji: String, removed: bool, from: AgentPubKey },
+    ReadReceipt { receipt: ReadReceipt, from: AgentPubKey },
+    Delivered { message: ActionHash, from: AgentPubKey },
+    // Hide the indicator at expires_at unless another Typing signal renews it
+    Typing { state: TypingState, expires_at: Timestamp, from: AgentPubKey },
     LinkCreated { action: SignedActionHashed, link_type: LinkTypes },
     LinkDeleted {
         action: SignedActionHashed,
@@ -76,10 +214,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                     GetOptions::default(),
                 )?
                 .ok_or(
-                    wasm_error!(
-                        WasmErrorInner::Guest("Failed to fetch CreateLink action"
-                        .to_string())
-                    ),
+                    RelayError::not_found("CreateLink", &delete_link.link_add_address),
                 )?;
             match record.action() {
                 Action::CreateLink(create_link) => {
@@ -97,9 +232,8 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                 }
                 _ => {
                     Err(
-                        wasm_error!(
-                            WasmErrorInner::Guest("Create Link should exist".to_string())
-                        ),
+                        RelayError::malformed("CreateLink", "expected a CreateLink action")
+                            .into(),
                     )
                 }
             }
@@ -166,48 +300,84 @@ fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTy
 }
 
 #[hdk_extern]
-pub fn generate_membrane_proof(input: MembraneProofData) -> ExternResult<SerializedBytes> {
+pub fn get_my_role() -> ExternResult<Role> {
+    let info = agent_info()?;
+    get_agent_role(info.agent_initial_pubkey, info.chain_head.0)
+}
+
+#[hdk_extern]
+pub fn generate_membrane_proof(mut input: MembraneProofData) -> ExternResult<SerializedBytes> {
+    freeze::check_conversation_open()?;
+    let Some(invite_delegation) = delegation::get_my_invite_delegations()? else {
+        return Err(RelayError::unauthorized("generate_membrane_proof").into());
+    };
+    if Role::from_membrane_proof_role(input.as_role).is_none() {
+        return Err(RelayError::UnknownRole { role: input.as_role }.into());
+    }
+    if !invite_delegation.chain.is_empty() && !can_delegate_role(input.as_role) {
+        return Err(RelayError::unauthorized("generate_membrane_proof").into());
+    }
     let me: HoloHash<holo_hash::hash_type::Agent> = agent_info()?.agent_latest_pubkey;
+    input.issued_after = Some(agent_info()?.chain_head.0);
 
     let result = MembraneProofEnvelope {
         signature: sign(me,input.clone())?,
         data: input,
+        handovers: invite_delegation.handovers,
+        delegations: invite_delegation.chain,
     };
     let proof = SerializedBytes::try_from(result).map_err(|e| wasm_error!(e))?;
     Ok(proof)
 }
 
+/// Get the membrane proof data an agent joined with, or None if they joined with an invite token
 #[hdk_extern]
 pub fn get_membrane_proof(agent: AgentPubKey) -> ExternResult<Option<MembraneProofData>> {
-    match get_details(agent, GetOptions::default())? {
+    match get_membrane_proof_for_agent(agent)? {
+        Some((MembraneProofKind::Agent(envelope), _)) => Ok(Some(envelope.data)),
+        _ => Ok(None),
+    }
+}
+
+/// Get the membrane proof an agent joined with, and the timestamp they joined at
+pub fn get_membrane_proof_for_agent(agent: AgentPubKey) -> ExternResult<Option<(MembraneProofKind, Timestamp)>> {
+    match get_details(agent.clone(), GetOptions::default())? {
         None => Ok(None),
         Some(details) => {
             match details {
                 Details::Entry(entry_details) => {
-                    let prev = entry_details.actions[0].action().prev_action().unwrap();
+                    let create_agent = entry_details
+                        .actions
+                        .first()
+                        .ok_or(RelayError::not_found("CreateAgent", &agent))?;
+                    let joined_at = create_agent.action().timestamp();
+                    let prev = create_agent
+                        .action()
+                        .prev_action()
+                        .ok_or(RelayError::malformed("CreateAgent", "action has no previous action"))?;
                     let maybe_record = get(prev.clone(), GetOptions::default())?;
                     match maybe_record {
-                        None => Err(wasm_error!("expected agent validation record")),
+                        None => Err(RelayError::not_found("AgentValidationPkg", prev).into()),
                         Some(record) => {
                             match record.action() {
                                 Action::AgentValidationPkg(
                                     AgentValidationPkg { membrane_proof, .. },
                                 ) => match membrane_proof {
                                     Some(proof) => {
-                                        let envelope = MembraneProofEnvelope::try_from((**proof).clone()).map_err(|e| wasm_error!(e))?;
-                                        Ok(Some(envelope.data))
+                                        let membrane_proof = MembraneProofKind::decode(proof.clone())?;
+                                        Ok(Some((membrane_proof, joined_at)))
                                     }
                                     None => Ok(None)
                                 },
                                 _ => {
-                                    Err(wasm_error!("expected AgentValidationPkg"))
+                                    Err(RelayError::malformed("AgentValidationPkg", "expected an AgentValidationPkg action").into())
                                 }
                             }
                         },
                     }
                 },
-                _ => Err(wasm_error!("unexpected entry type"))
+                _ => Err(RelayError::malformed("AgentPubKey", "expected an entry").into())
             }
         }
     }
-}
\ No newline at end of file
+}
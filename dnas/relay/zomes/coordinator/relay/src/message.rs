@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+
 use hdk::prelude::*;
 use relay_integrity::*;
 
+use crate::error::RelayError;
+
+use crate::delivery::{get_delivery_recipients, send_message_to_agents};
+use crate::freeze::{check_conversation_open, filter_frozen_messages};
 use crate::get_entry_for_action;
+use crate::remote_signal::{send_relay_signal, MessageDeleted, RemoteSignal};
+use crate::revocation::filter_revoked_authors;
+use crate::schema::decode_versioned;
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -11,14 +20,20 @@ pub struct SendMessageInput {
 }
 
 #[hdk_extern]
-pub fn create_message(input: SendMessageInput) -> ExternResult<Record> {
+pub fn get_bucket_for_timestamp(timestamp: Timestamp) -> ExternResult<u32> {
+    bucket_for_timestamp(timestamp)
+}
+
+#[hdk_extern]
+pub fn create_message(mut input: SendMessageInput) -> ExternResult<Record> {
+    input.message.status = check_conversation_open()?;
+    // The bucket is validated against the action timestamp, so don't trust the one computed by the client
+    input.message.bucket = bucket_for_timestamp(sys_time()?)?;
+    input.message.schema_version = Message::SCHEMA_VERSION;
     let message_hash = create_entry(&EntryTypes::Message(input.message.clone()))?;
     let record = get(message_hash.clone(), GetOptions::default())?
         .ok_or(
-            wasm_error!(
-                WasmErrorInner::Guest("Could not find the newly created Message"
-                .to_string())
-            ),
+            RelayError::not_created("Message"),
         )?;
 
     let path = messages_path(input.message.bucket);
@@ -30,14 +45,11 @@ pub fn create_message(input: SendMessageInput) -> ExternResult<Record> {
         (),
     )?;
 
-    let _ = send_remote_signal(
-        MessageRecord {
-            message: Some(input.message),
-            original_action: message_hash.clone(),
-            signed_action: record.signed_action().clone()
-        },
-        input.agents,
-    );
+    if let Some(parent) = input.message.reply_to.clone() {
+        create_link(parent, message_hash.clone(), LinkTypes::MessageToReplies, ())?;
+    }
+
+    send_message_to_agents(message_hash.clone(), input.agents)?;
 
     debug!("create message all messages link: {:?}", link);
     Ok(record)
@@ -94,7 +106,7 @@ pub fn get_message_entries(hashes: Vec<ActionHash>) -> ExternResult<Vec<MessageR
             results.push (r);
         }
     }
-    Ok(results)
+    filter_frozen_messages(filter_revoked_authors(results)?)
 }
 
 #[hdk_extern]
@@ -108,44 +120,25 @@ pub fn get_messages_for_buckets(buckets: Vec<u32>) -> ExternResult<Vec<MessageRe
         }
     }
 
-    Ok(results)
+    filter_frozen_messages(filter_revoked_authors(results)?)
 }
 
+/// Get the latest revision of a message, which is the last of `get_all_revisions_for_message`,
+/// or None if the message was deleted
 #[hdk_extern]
 pub fn get_latest_message(
     original_message_hash: ActionHash,
 ) -> ExternResult<Option<MessageRecord>> {
-    let links = get_links(
-        GetLinksInputBuilder::try_new(
-                original_message_hash.clone(),
-                LinkTypes::MessageUpdates,
-            )?
-            .build(),
-    )?;
-    let latest_link = links
-        .into_iter()
-        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
-    let latest_message_hash = match latest_link {
-        Some(link) => {
-            link.target
-                .clone()
-                .into_action_hash()
-                .ok_or(
-                    wasm_error!(
-                        WasmErrorInner::Guest("No action hash associated with link"
-                        .to_string())
-                    ),
-                )?
-        }
-        None => original_message_hash.clone(),
-    };
-
-    match get(latest_message_hash, GetOptions::default())? {
+    if get(original_message_hash.clone(), GetOptions::default())?.is_none() {
+        return Ok(None);
+    }
+    let revisions = get_all_revisions_for_message(original_message_hash.clone())?;
+    match revisions.last() {
         Some(record) => {
             Ok(Some(MessageRecord {
                 original_action: original_message_hash,
                 signed_action: record.signed_action().clone(),
-                message: record.entry().to_app_option().map_err(|e| wasm_error!(e))?,
+                message: decode_versioned(record)?,
             }))
         },
         None => Ok(None)
@@ -162,15 +155,16 @@ pub fn get_original_message(
     match details {
         Details::Record(details) => Ok(Some(details.record)),
         _ => {
-            Err(
-                wasm_error!(
-                    WasmErrorInner::Guest("Malformed get details response".to_string())
-                ),
-            )
+            Err(RelayError::malformed("Message", "expected a record").into())
         }
     }
 }
 
+/// Get the original message followed by its updates, ordered so the last one is the latest revision.
+///
+/// Updates form a tree rooted at the original message, since two edits can branch from the same revision.
+/// Revisions are ordered by the length of their edit chain, then by timestamp, then by action hash,
+/// so the latest revision is the tip of the longest edit chain, and every agent picks the same one.
 #[hdk_extern]
 pub fn get_all_revisions_for_message(
     original_message_hash: ActionHash,
@@ -194,10 +188,7 @@ pub fn get_all_revisions_for_message(
                     .target
                     .into_action_hash()
                     .ok_or(
-                        wasm_error!(
-                            WasmErrorInner::Guest("No action hash associated with link"
-                            .to_string())
-                        ),
+                        RelayError::invalid_link_target("MessageUpdates"),
                     )?
                     .into(),
                 GetOptions::default(),
@@ -205,7 +196,32 @@ pub fn get_all_revisions_for_message(
         ))
         .collect::<ExternResult<Vec<GetInput>>>()?;
     let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
-    let mut records: Vec<Record> = records.into_iter().flatten().collect();
+    let mut updates: Vec<Record> = records.into_iter().flatten().collect();
+
+    // Updates are all authored by the message's author, so each one comes after its previous revision in their chain
+    updates.sort_by_key(|record| record.action().action_seq());
+    updates.dedup_by(|a, b| a.action_address() == b.action_address());
+    let mut depths: HashMap<ActionHash, usize> = HashMap::new();
+    depths.insert(original_message_hash, 0);
+    let mut revisions: Vec<(usize, Record)> = Vec::new();
+    for record in updates {
+        let Action::Update(update) = record.action() else {
+            continue;
+        };
+        let Some(previous_depth) = depths.get(&update.original_action_address).copied() else {
+            continue;
+        };
+        depths.insert(record.action_address().clone(), previous_depth + 1);
+        revisions.push((previous_depth + 1, record));
+    }
+    revisions.sort_by(|(depth_a, record_a), (depth_b, record_b)| {
+        depth_a
+            .cmp(depth_b)
+            .then_with(|| record_a.action().timestamp().cmp(&record_b.action().timestamp()))
+            .then_with(|| record_a.action_address().cmp(record_b.action_address()))
+    });
+
+    let mut records: Vec<Record> = revisions.into_iter().map(|(_, record)| record).collect();
     records.insert(0, original_record);
     Ok(records)
 }
@@ -217,7 +233,9 @@ pub struct UpdateMessageInput {
     pub updated_message: Message,
 }
 #[hdk_extern]
-pub fn update_message(input: UpdateMessageInput) -> ExternResult<Record> {
+pub fn update_message(mut input: UpdateMessageInput) -> ExternResult<Record> {
+    input.updated_message.status = check_conversation_open()?;
+    input.updated_message.schema_version = Message::SCHEMA_VERSION;
     let updated_message_hash = update_entry(
         input.previous_message_hash.clone(),
         &input.updated_message,
@@ -230,11 +248,17 @@ pub fn update_message(input: UpdateMessageInput) -> ExternResult<Record> {
     )?;
     let record = get(updated_message_hash.clone(), GetOptions::default())?
         .ok_or(
-            wasm_error!(
-                WasmErrorInner::Guest("Could not find the newly updated Message"
-                .to_string())
-            ),
+            RelayError::not_created("Message"),
         )?;
+    // Notify the agents the message was sent to
+    let _ = send_relay_signal(
+        RemoteSignal::MessageUpdated(MessageRecord {
+            message: Some(input.updated_message),
+            original_action: input.original_message_hash.clone(),
+            signed_action: record.signed_action().clone(),
+        }),
+        get_delivery_recipients(&input.original_message_hash)?,
+    );
     Ok(record)
 }
 
@@ -245,19 +269,17 @@ pub fn delete_message(original_message_hash: ActionHash) -> ExternResult<ActionH
         match app_entry {
             EntryTypes::Message(message) => Ok(message),
             _=> Err(
-                wasm_error!(
-                    WasmErrorInner::Guest("Malformed get details response".to_string())
-                ),
+                RelayError::malformed("Message", "entry is not a Message"),
             )
         }
     } else {
         Err(
-            wasm_error!(
-                WasmErrorInner::Guest("Entry not found".to_string())
-            ),
+            RelayError::not_found("Message", &original_message_hash),
         )
     }?;
 
+    // Only the link's author can delete it, so a moderator deleting a message leaves its link in place
+    let me = agent_info()?.agent_initial_pubkey;
     let path = messages_path(message.bucket);
     let links = get_links(
         GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllMessages)?
@@ -265,12 +287,35 @@ pub fn delete_message(original_message_hash: ActionHash) -> ExternResult<ActionH
     )?;
     for link in links {
         if let Some(hash) = link.target.into_action_hash() {
-            if hash.eq(&original_message_hash) {
+            if hash.eq(&original_message_hash) && link.author == me {
                 delete_link(link.create_link_hash)?;
             }
         }
     }
-    delete_entry(original_message_hash)
+    if let Some(parent) = message.reply_to {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(parent, LinkTypes::MessageToReplies)?.build(),
+        )?;
+        for link in links {
+            if let Some(hash) = link.target.into_action_hash() {
+                if hash.eq(&original_message_hash) && link.author == me {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    let delete_hash = delete_entry(original_message_hash.clone())?;
+    // Notify the agents the message was sent to, which is nobody when a moderator deletes it
+    if let Some(delete) = get(delete_hash.clone(), GetOptions::default())? {
+        let _ = send_relay_signal(
+            RemoteSignal::MessageDeleted(MessageDeleted {
+                message: original_message_hash.clone(),
+                delete: delete.signed_action().clone(),
+            }),
+            get_delivery_recipients(&original_message_hash)?,
+        );
+    }
+    Ok(delete_hash)
 }
 
 #[hdk_extern]
@@ -282,7 +327,7 @@ pub fn get_all_deletes_for_message(
     };
     match details {
         Details::Entry(_) => {
-            Err(wasm_error!(WasmErrorInner::Guest("Malformed details".into())))
+            Err(RelayError::malformed("Message", "expected a record").into())
         }
         Details::Record(record_details) => Ok(Some(record_details.deletes)),
     }
@@ -0,0 +1,294 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+
+use crate::get_my_role;
+
+// How long a successor has to accept a handover
+pub const HANDOVER_EXPIRY_MS: i64 = 1000 * 60 * 60 * 24 * 7; // 7 days
+
+/// Propose `successor` as the new owner. I give up ownership now, and can cancel until they accept.
+#[hdk_extern]
+pub fn propose_ownership_handover(successor: AgentPubKey) -> ExternResult<Record> {
+    let Some(mut chain) = get_my_ownership_handovers()? else {
+        return Err(RelayError::unauthorized("propose_ownership_handover").into());
+    };
+    let me = agent_info()?.agent_latest_pubkey;
+    let expires_at = Timestamp::from_micros(sys_time()?.as_micros() + HANDOVER_EXPIRY_MS * 1000);
+    let data = OwnershipHandoverData {
+        conversation_id: dna_info()?.modifiers.network_seed,
+        successor: successor.clone(),
+        expires_at: Some(expires_at),
+    };
+    chain.push(OwnershipHandoverEnvelope {
+        signature: sign(me, data.clone())?,
+        data,
+    });
+    let ownership_handover_hash = create_entry(
+        &EntryTypes::OwnershipHandover(OwnershipHandover { chain }),
+    )?;
+    create_link(
+        successor,
+        ownership_handover_hash.clone(),
+        LinkTypes::AgentToOwnershipHandovers,
+        (),
+    )?;
+    let record = get(ownership_handover_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("OwnershipHandover"),
+        )?;
+    Ok(record)
+}
+
+/// Cancel a handover I proposed which has not been accepted yet.
+///
+/// I get ownership back with `reclaim_ownership` once the handover expired.
+#[hdk_extern]
+pub fn cancel_ownership_handover(handover_hash: ActionHash) -> ExternResult<ActionHash> {
+    if find_acceptance(&handover_hash)?.is_some() || is_handover_cancelled(&handover_hash, None)? {
+        return Err(RelayError::handover_not_pending(&handover_hash).into());
+    }
+    delete_entry(handover_hash)
+}
+
+/// Handovers proposed to `agent` which have not been cancelled
+#[hdk_extern]
+pub fn get_ownership_handovers_for_agent(agent: AgentPubKey) -> ExternResult<Vec<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToOwnershipHandovers)?.build(),
+    )?;
+    let mut records: Vec<Record> = Vec::new();
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        if is_handover_cancelled(&hash, None)? {
+            continue;
+        }
+        if let Some(record) = get(hash, GetOptions::default())? {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+#[hdk_extern]
+pub fn accept_ownership_handover(handover_hash: ActionHash) -> ExternResult<Record> {
+    if is_handover_cancelled(&handover_hash, None)? {
+        return Err(RelayError::handover_not_pending(&handover_hash).into());
+    }
+    let Some(handover_record) = get(handover_hash.clone(), GetOptions::default())? else {
+        return Err(RelayError::not_found("OwnershipHandover", &handover_hash).into());
+    };
+    let proposer_chain_top = get_chain_top(handover_record.action().author().clone())?;
+    let ownership_acceptance_hash = create_entry(
+        &EntryTypes::OwnershipAcceptance(OwnershipAcceptance {
+            handover_hash,
+            proposer_chain_top,
+        }),
+    )?;
+    create_link(
+        Path::from(OWNERSHIP_PATH).path_entry_hash()?,
+        ownership_acceptance_hash.clone(),
+        LinkTypes::OwnershipAcceptances,
+        (),
+    )?;
+    let record = get(ownership_acceptance_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("OwnershipAcceptance"),
+        )?;
+    Ok(record)
+}
+
+/// Take ownership back after a handover I proposed expired without being accepted
+#[hdk_extern]
+pub fn reclaim_ownership(handover_hash: ActionHash) -> ExternResult<Record> {
+    let Some(handover_record) = get(handover_hash.clone(), GetOptions::default())? else {
+        return Err(RelayError::not_found("OwnershipHandover", &handover_hash).into());
+    };
+    let Some(ownership_handover) = handover_record
+        .entry()
+        .to_app_option::<OwnershipHandover>()
+        .map_err(|e| wasm_error!(e))?
+    else {
+        return Err(RelayError::malformed("OwnershipHandover", "record has no OwnershipHandover entry").into());
+    };
+    let Some(successor) = ownership_handover.successor() else {
+        return Err(RelayError::malformed("OwnershipHandover", "handover chain is empty").into());
+    };
+    // Validation needs an action the successor made after the handover expired
+    let successor_chain_top = get_chain_top(successor.clone())?;
+    let expires_at = ownership_handover.chain.last().and_then(|envelope| envelope.data.expires_at);
+    let successor_chain_top_at = get(successor_chain_top.clone(), GetOptions::default())?
+        .map(|record| record.action().timestamp());
+    if !expires_at.is_some_and(|expires_at| successor_chain_top_at.is_some_and(|at| at > expires_at)) {
+        return Err(RelayError::handover_not_expired(&handover_hash).into());
+    }
+    let ownership_reclaim_hash = create_entry(
+        &EntryTypes::OwnershipReclaim(OwnershipReclaim {
+            handover_hash,
+            successor_chain_top,
+        }),
+    )?;
+    let record = get(ownership_reclaim_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("OwnershipReclaim"),
+        )?;
+    Ok(record)
+}
+
+/// The latest action in an agent's source chain seen by the network
+fn get_chain_top(agent: AgentPubKey) -> ExternResult<ActionHash> {
+    let activity = get_agent_activity(agent, ChainQueryFilter::new(), ActivityRequest::Full)?;
+    activity
+        .valid_activity
+        .into_iter()
+        .max_by_key(|(action_seq, _)| *action_seq)
+        .map(|(_, action_hash)| action_hash)
+        .ok_or(
+            RelayError::NotFound {
+                kind: "AgentActivity".to_string(),
+                hash: None,
+            }
+            .into(),
+        )
+}
+
+/// The current owner: the successor of the longest accepted handover chain, or the progenitor.
+///
+/// While a handover is pending nobody owns the conversation, this returns the previous owner until it is accepted.
+#[hdk_extern]
+pub fn get_current_owner() -> ExternResult<Option<AgentPubKey>> {
+    let Some(props) = dna_properties()? else {
+        return Ok(None);
+    };
+    let owner = match get_latest_accepted_handover(None)? {
+        Some(accepted) => accepted.successor().cloned(),
+        None => Some(props.progenitor),
+    };
+    Ok(owner)
+}
+
+/// Whether `handovers` is the chain of the owner at `timestamp`, so that proofs signed by former owners,
+/// or under a chain that was superseded before the agent joined, are not honoured
+pub fn is_current_handover_chain(
+    handovers: &[OwnershipHandoverEnvelope],
+    timestamp: Timestamp,
+) -> ExternResult<bool> {
+    let current = get_latest_accepted_handover(Some(timestamp))?
+        .map(|accepted| accepted.chain)
+        .unwrap_or_default();
+    Ok(current.as_slice() == handovers)
+}
+
+/// The accepted handover with the longest chain, only counting acceptances before `before` when set
+fn get_latest_accepted_handover(before: Option<Timestamp>) -> ExternResult<Option<OwnershipHandover>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+                Path::from(OWNERSHIP_PATH).path_entry_hash()?,
+                LinkTypes::OwnershipAcceptances,
+            )?
+            .build(),
+    )?;
+    let mut result: Option<OwnershipHandover> = None;
+    for link in links {
+        if before.is_some_and(|before| link.timestamp >= before) {
+            continue;
+        }
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        let Some(accepted) = get_accepted_handover(hash)? else {
+            continue;
+        };
+        if !result
+            .as_ref()
+            .is_some_and(|r| r.chain.len() >= accepted.chain.len())
+        {
+            result = Some(accepted);
+        }
+    }
+    Ok(result)
+}
+
+/// The handover accepted by an acceptance, None if it was cancelled before being accepted
+fn get_accepted_handover(
+    ownership_acceptance_hash: ActionHash,
+) -> ExternResult<Option<OwnershipHandover>> {
+    let Some(record) = get(ownership_acceptance_hash.clone(), GetOptions::default())? else {
+        return Ok(None);
+    };
+    let Some(ownership_acceptance) = record
+        .entry()
+        .to_app_option::<OwnershipAcceptance>()
+        .map_err(|e| wasm_error!(e))?
+    else {
+        return Ok(None);
+    };
+    let accepted_at = record.action().timestamp();
+    if is_handover_cancelled(&ownership_acceptance.handover_hash, Some(accepted_at))? {
+        return Ok(None);
+    }
+    let Some(record) = get(ownership_acceptance.handover_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    let Some(handover) = record.entry().to_app_option().map_err(|e| wasm_error!(e))? else {
+        return Ok(None);
+    };
+    Ok(Some(handover))
+}
+
+/// Whether the proposer deleted a handover, before `before` when set
+fn is_handover_cancelled(handover_hash: &ActionHash, before: Option<Timestamp>) -> ExternResult<bool> {
+    let Some(Details::Record(details)) = get_details(handover_hash.clone(), GetOptions::default())?
+    else {
+        return Ok(false);
+    };
+    Ok(details
+        .deletes
+        .iter()
+        .any(|delete| !before.is_some_and(|before| delete.action().timestamp >= before)))
+}
+
+/// The acceptance of a handover, if any
+fn find_acceptance(handover_hash: &ActionHash) -> ExternResult<Option<ActionHash>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+                Path::from(OWNERSHIP_PATH).path_entry_hash()?,
+                LinkTypes::OwnershipAcceptances,
+            )?
+            .build(),
+    )?;
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        let Some(record) = get(hash.clone(), GetOptions::default())? else {
+            continue;
+        };
+        let ownership_acceptance: Option<OwnershipAcceptance> = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?;
+        if ownership_acceptance.is_some_and(|a| a.handover_hash == *handover_hash) {
+            return Ok(Some(hash));
+        }
+    }
+    Ok(None)
+}
+
+/// The handover chain to include in the proofs I sign: empty for the progenitor,
+/// None if I am not the owner
+pub fn get_my_ownership_handovers() -> ExternResult<Option<Vec<OwnershipHandoverEnvelope>>> {
+    if !get_my_role(())?.can_invite() {
+        return Ok(None);
+    }
+    let acceptances = query(
+        ChainQueryFilter::new()
+            .entry_type(UnitEntryTypes::OwnershipAcceptance.try_into()?)
+            .include_entries(true),
+    )?;
+    let Some(record) = acceptances.last() else {
+        return Ok(Some(Vec::new()));
+    };
+    let accepted = get_accepted_handover(record.action_address().clone())?
+        .ok_or(
+            RelayError::not_found("OwnershipHandover", record.action_address()),
+        )?;
+    Ok(Some(accepted.chain))
+}
@@ -0,0 +1,182 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+use crate::freeze::{check_conversation_open, get_conversation_freeze};
+use crate::remote_signal::{send_relay_signal, RemoteSignal};
+use crate::revocation::RevocationCache;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReactionInput {
+    // Original action hash of the message
+    pub message: ActionHash,
+    pub emoji: String,
+    // Agents to notify with a remote signal
+    pub agents: Vec<AgentPubKey>,
+}
+
+/// Sent to the other agents in the conversation when a reaction is added or removed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReactionUpdate {
+    pub message: ActionHash,
+    pub emoji: String,
+    pub removed: bool,
+    // The CreateLink adding the reaction, or the DeleteLink removing it
+    pub action: SignedActionHashed,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Reaction {
+    pub emoji: String,
+    pub agent: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MessageReactions {
+    pub message: ActionHash,
+    pub reactions: Vec<Reaction>,
+}
+
+fn get_reaction_links(message: ActionHash, emoji: Option<&str>) -> ExternResult<Vec<Link>> {
+    let mut input = GetLinksInputBuilder::try_new(message, LinkTypes::MessageToReactions)?;
+    if let Some(emoji) = emoji {
+        input = input.tag_prefix(LinkTag::new(emoji.as_bytes().to_vec()));
+    }
+    Ok(get_links(input.build())?
+        .into_iter()
+        .filter(|link| !emoji.is_some_and(|emoji| link.tag.0 != emoji.as_bytes()))
+        .collect())
+}
+
+/// React to a message with an emoji, doing nothing if I already reacted with it
+#[hdk_extern]
+pub fn add_reaction(input: ReactionInput) -> ExternResult<ActionHash> {
+    check_conversation_open()?;
+    if reaction_from_tag(&LinkTag::new(input.emoji.as_bytes().to_vec())).is_none() {
+        return Err(RelayError::malformed("Reaction", "not an emoji, or too long").into());
+    }
+    let me = agent_info()?.agent_initial_pubkey;
+    if let Some(link) = get_reaction_links(input.message.clone(), Some(&input.emoji))?
+        .into_iter()
+        .find(|link| link.author == me)
+    {
+        return Ok(link.create_link_hash);
+    }
+    let create_link_hash = create_link(
+        input.message.clone(),
+        me,
+        LinkTypes::MessageToReactions,
+        LinkTag::new(input.emoji.as_bytes().to_vec()),
+    )?;
+    let record = get(create_link_hash.clone(), GetOptions::default())?
+        .ok_or(RelayError::not_created("CreateLink"))?;
+    let _ = send_relay_signal(
+        RemoteSignal::Reaction(ReactionUpdate {
+            message: input.message,
+            emoji: input.emoji,
+            removed: false,
+            action: record.signed_action,
+        }),
+        input.agents,
+    );
+    Ok(create_link_hash)
+}
+
+#[hdk_extern]
+pub fn remove_reaction(input: ReactionInput) -> ExternResult<()> {
+    check_conversation_open()?;
+    let me = agent_info()?.agent_initial_pubkey;
+    let links: Vec<Link> = get_reaction_links(input.message.clone(), Some(&input.emoji))?
+        .into_iter()
+        .filter(|link| link.author == me)
+        .collect();
+    let mut delete_link_hash: Option<ActionHash> = None;
+    for link in links {
+        delete_link_hash = Some(delete_link(link.create_link_hash)?);
+    }
+    let Some(delete_link_hash) = delete_link_hash else {
+        return Ok(());
+    };
+    let record = get(delete_link_hash, GetOptions::default())?
+        .ok_or(RelayError::not_created("DeleteLink"))?;
+    let _ = send_relay_signal(
+        RemoteSignal::Reaction(ReactionUpdate {
+            message: input.message,
+            emoji: input.emoji,
+            removed: true,
+            action: record.signed_action,
+        }),
+        input.agents,
+    );
+    Ok(())
+}
+
+/// Get the reactions of each message, oldest first,
+/// leaving out those by revoked agents and those added after the conversation was frozen
+#[hdk_extern]
+pub fn get_reactions_for_messages(
+    messages: Vec<ActionHash>,
+) -> ExternResult<Vec<MessageReactions>> {
+    let is_private = dna_properties()?.is_some_and(|props| props.privacy == Privacy::Private);
+    let frozen_at = get_conversation_freeze(())?.map(|record| record.action().timestamp());
+    let mut revoked = RevocationCache::default();
+    let mut results: Vec<MessageReactions> = Vec::new();
+    for message in messages {
+        let mut links = get_reaction_links(message.clone(), None)?;
+        links.sort_by(|link_a, link_b| {
+            link_a
+                .timestamp
+                .cmp(&link_b.timestamp)
+                .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+        });
+        let mut reactions: Vec<Reaction> = Vec::new();
+        for link in links {
+            if frozen_at.is_some_and(|frozen_at| link.timestamp >= frozen_at) {
+                continue;
+            }
+            let Some(emoji) = reaction_from_tag(&link.tag) else {
+                continue;
+            };
+            if reactions.iter().any(|r| r.emoji == emoji && r.agent == link.author) {
+                continue;
+            }
+            if is_private {
+                if revoked.is_revoked(&link.author)? {
+                    continue;
+                }
+            }
+            reactions.push(Reaction {
+                emoji,
+                agent: link.author,
+                timestamp: link.timestamp,
+            });
+        }
+        results.push(MessageReactions { message, reactions });
+    }
+    Ok(results)
+}
+
+/// Whether `update` carries the link action of `from` adding or removing the reaction it describes
+pub fn verify_reaction_update(from: &AgentPubKey, update: &ReactionUpdate) -> ExternResult<bool> {
+    let tag = LinkTag::new(update.emoji.as_bytes().to_vec());
+    let create_link = match update.action.action() {
+        Action::CreateLink(create_link) if !update.removed => create_link.clone(),
+        Action::DeleteLink(delete_link) if update.removed => {
+            if delete_link.base_address != update.message.clone().into() {
+                return Ok(false);
+            }
+            let Some(record) = get(delete_link.link_add_address.clone(), GetOptions::default())? else {
+                return Ok(false);
+            };
+            let Action::CreateLink(create_link) = record.action() else {
+                return Ok(false);
+            };
+            create_link.clone()
+        }
+        _ => return Ok(false),
+    };
+    Ok(create_link.base_address == update.message.clone().into()
+        && create_link.target_address == from.clone().into()
+        && create_link.tag == tag)
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+use crate::remote_signal::{send_relay_signal, RemoteSignal};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkReadInput {
+    // Original action hash of the last message read
+    pub message: ActionHash,
+    // Share a read receipt with the other members
+    pub share: bool,
+    // Agents to notify with a remote signal when sharing
+    pub agents: Vec<AgentPubKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Reader {
+    pub agent: AgentPubKey,
+    // When the agent shared the receipt covering the message
+    pub read_at: Timestamp,
+}
+
+fn get_read_marker_record() -> ExternResult<Option<Record>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type(UnitEntryTypes::ReadMarker.try_into()?)
+            .include_entries(true),
+    )?;
+    Ok(records.last().cloned())
+}
+
+/// Get the last message I read in this conversation
+#[hdk_extern]
+pub fn get_read_state() -> ExternResult<Option<ReadMarker>> {
+    let Some(record) = get_read_marker_record()? else {
+        return Ok(None);
+    };
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))
+}
+
+/// Move my read marker forward to `message`, optionally sharing a read receipt with the other members.
+///
+/// The marker never moves backwards, so marking an older message as read leaves it unchanged.
+#[hdk_extern]
+pub fn mark_read(input: MarkReadInput) -> ExternResult<ReadMarker> {
+    let action = get(input.message.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_found("Message", &input.message),
+        )?;
+    let read_marker = ReadMarker {
+        last_read: input.message,
+        last_read_at: action.action().timestamp(),
+    };
+    match get_read_marker_record()? {
+        Some(record) => {
+            let current: Option<ReadMarker> = record
+                .entry()
+                .to_app_option()
+                .map_err(|e| wasm_error!(e))?;
+            if let Some(current) = current {
+                if current.last_read_at >= read_marker.last_read_at {
+                    return Ok(current);
+                }
+            }
+            update_entry(record.action_address().clone(), &EntryTypes::ReadMarker(read_marker.clone()))?;
+        }
+        None => {
+            create_entry(&EntryTypes::ReadMarker(read_marker.clone()))?;
+        }
+    }
+    if input.share {
+        share_read_receipt(&read_marker, input.agents)?;
+    }
+    Ok(read_marker)
+}
+
+fn share_read_receipt(read_marker: &ReadMarker, agents: Vec<AgentPubKey>) -> ExternResult<()> {
+    let me = agent_info()?.agent_initial_pubkey;
+    let receipt = ReadReceipt {
+        conversation_id: dna_info()?.modifiers.network_seed,
+        last_read: read_marker.last_read.clone(),
+        last_read_at: read_marker.last_read_at,
+    };
+    let path_entry_hash = Path::from(READ_RECEIPTS_PATH).path_entry_hash()?;
+    // Only my latest receipt matters, so replace the previous ones
+    let links = get_links(
+        GetLinksInputBuilder::try_new(path_entry_hash.clone(), LinkTypes::ReadReceipts)?.build(),
+    )?;
+    for link in links {
+        if link.author == me {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    create_link(path_entry_hash, me.clone(), LinkTypes::ReadReceipts, receipt.to_tag()?)?;
+    let _ = send_relay_signal(
+        RemoteSignal::ReadReceipt(ReadReceiptEnvelope {
+            signature: sign(me, receipt.clone())?,
+            receipt,
+        }),
+        agents,
+    );
+    Ok(())
+}
+
+/// Get the agents who shared a read receipt covering `message`
+#[hdk_extern]
+pub fn get_readers_for_message(message: ActionHash) -> ExternResult<Vec<Reader>> {
+    let action = get(message.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_found("Message", &message),
+        )?;
+    let message_at = action.action().timestamp();
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+                Path::from(READ_RECEIPTS_PATH).path_entry_hash()?,
+                LinkTypes::ReadReceipts,
+            )?
+            .build(),
+    )?;
+    // Latest receipt of each agent, as deleting the previous ones may not have reached us yet
+    let mut latest: HashMap<AgentPubKey, (ReadReceipt, Timestamp)> = HashMap::new();
+    for link in links {
+        let Ok(receipt) = ReadReceipt::from_tag(&link.tag) else {
+            continue;
+        };
+        if latest
+            .get(&link.author)
+            .is_some_and(|(r, _)| r.last_read_at >= receipt.last_read_at)
+        {
+            continue;
+        }
+        latest.insert(link.author, (receipt, link.timestamp));
+    }
+    let mut readers: Vec<Reader> = latest
+        .into_iter()
+        .filter(|(_, (receipt, _))| receipt.last_read_at >= message_at)
+        .map(|(agent, (_, read_at))| Reader { agent, read_at })
+        .collect();
+    readers.sort_by(|a, b| a.read_at.cmp(&b.read_at));
+    Ok(readers)
+}
+
+/// Check a read receipt received as a remote signal was signed by its sender for this conversation
+pub fn verify_read_receipt(
+    from: AgentPubKey,
+    envelope: &ReadReceiptEnvelope,
+) -> ExternResult<bool> {
+    if envelope.receipt.conversation_id != dna_info()?.modifiers.network_seed {
+        return Ok(false);
+    }
+    verify_signature(from, envelope.signature.clone(), envelope.receipt.clone())
+}
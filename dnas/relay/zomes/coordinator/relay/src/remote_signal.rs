@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::delivery::DeliveryAck;
+use crate::get_membrane_proof_for_agent;
+use crate::reaction::{verify_reaction_update, ReactionUpdate};
+use crate::read::verify_read_receipt;
+use crate::revocation::is_agent_revoked;
+use crate::typing::TypingUpdate;
+
+/// Version of the remote signal protocol, sent in every envelope.
+///
+/// Bumped when the payload of an existing kind changes. Adding a kind does not need a bump,
+/// as agents who don't know it ignore it.
+pub const REMOTE_SIGNAL_PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MessageDeleted {
+    // Original action hash of the message
+    pub message: ActionHash,
+    pub delete: SignedActionHashed,
+}
+
+/// Kinds of remote signal, tagged so new kinds can be added without breaking agents on older versions
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RemoteSignal {
+    Message(MessageRecord),
+    MessageUpdated(MessageRecord),
+    MessageDeleted(MessageDeleted),
+    Reaction(ReactionUpdate),
+    ReadReceipt(ReadReceiptEnvelope),
+    DeliveryAck(DeliveryAck),
+    Typing(TypingUpdate),
+    // A kind sent by a newer version of the relay zome
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoteSignalEnvelope {
+    pub protocol_version: u32,
+    pub signal: RemoteSignal,
+}
+
+/// Anything `recv_remote_signal` accepts
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum ReceivedSignal {
+    Envelope(RemoteSignalEnvelope),
+    // Sent by agents from before the envelope was introduced, who only sent new messages
+    Legacy(MessageRecord),
+}
+
+impl ReceivedSignal {
+    pub fn protocol_version(&self) -> u32 {
+        match self {
+            ReceivedSignal::Envelope(envelope) => envelope.protocol_version,
+            ReceivedSignal::Legacy(_) => 0,
+        }
+    }
+
+    pub fn into_signal(self) -> RemoteSignal {
+        match self {
+            ReceivedSignal::Envelope(envelope) => envelope.signal,
+            ReceivedSignal::Legacy(message_record) => RemoteSignal::Message(message_record),
+        }
+    }
+}
+
+/// Send `signal` to `agents` in an envelope with the current protocol version
+pub fn send_relay_signal(signal: RemoteSignal, agents: Vec<AgentPubKey>) -> ExternResult<()> {
+    send_remote_signal(
+        RemoteSignalEnvelope {
+            protocol_version: REMOTE_SIGNAL_PROTOCOL_VERSION,
+            signal,
+        },
+        agents,
+    )
+}
+
+/// Whether `agent` joined this conversation: their source chain is valid and,
+/// in private conversations, they joined with a membrane proof which was not revoked.
+pub fn is_conversation_member(agent: &AgentPubKey) -> ExternResult<bool> {
+    MembershipCache::default().is_member(agent)
+}
+
+/// Members checked during a zome call, so signals to several agents only check each of them once.
+///
+/// Kept in memory only: receiving or sending a signal never writes to the source chain.
+#[derive(Default)]
+pub struct MembershipCache {
+    checked: HashMap<AgentPubKey, bool>,
+}
+
+impl MembershipCache {
+    pub fn is_member(&mut self, agent: &AgentPubKey) -> ExternResult<bool> {
+        if let Some(is_member) = self.checked.get(agent) {
+            return Ok(*is_member);
+        }
+        let is_member = check_conversation_member(agent)?;
+        self.checked.insert(agent.clone(), is_member);
+        Ok(is_member)
+    }
+}
+
+fn check_conversation_member(agent: &AgentPubKey) -> ExternResult<bool> {
+    let Some(props) = dna_properties()? else {
+        return Ok(false);
+    };
+    if *agent == props.progenitor {
+        return Ok(true);
+    }
+    let activity = get_agent_activity(
+        agent.clone(),
+        ChainQueryFilter::new(),
+        ActivityRequest::Status,
+    )?;
+    if !matches!(activity.status, ChainStatus::Valid(_)) {
+        return Ok(false);
+    }
+    if props.privacy == Privacy::Public {
+        return Ok(true);
+    }
+    if get_membrane_proof_for_agent(agent.clone())?.is_none() {
+        return Ok(false);
+    }
+    Ok(!is_agent_revoked(agent.clone())?)
+}
+
+/// Whether `signed_action` was authored and signed by `from`, and its hash matches the action
+fn verify_signed_action(from: &AgentPubKey, signed_action: &SignedActionHashed) -> ExternResult<bool> {
+    let action = signed_action.action();
+    if action.author() != from {
+        return Ok(false);
+    }
+    if hash_action(action.clone())? != *signed_action.action_address() {
+        return Ok(false);
+    }
+    verify_signature(from.clone(), signed_action.signature().clone(), action)
+}
+
+// How many edits back a signal's update is followed to find the original message
+const MAX_EDIT_CHAIN: usize = 64;
+
+/// Whether `entry_hash` is the hash of `message`
+fn is_message_entry(message: &Option<Message>, entry_hash: Option<&EntryHash>) -> ExternResult<bool> {
+    let (Some(message), Some(entry_hash)) = (message, entry_hash) else {
+        return Ok(false);
+    };
+    Ok(hash_entry(message)? == *entry_hash)
+}
+
+/// Whether `message_hash` is the action creating an original Message authored by `from`
+fn is_original_message_by(from: &AgentPubKey, message_hash: &ActionHash) -> ExternResult<bool> {
+    let Some(record) = get(message_hash.clone(), GetOptions::default())? else {
+        return Ok(false);
+    };
+    let message_type: EntryType = UnitEntryTypes::Message.try_into()?;
+    Ok(matches!(
+        record.action(),
+        Action::Create(create) if create.author == *from && create.entry_type == message_type
+    ))
+}
+
+/// Whether `update` is an edit by `from` of the original message `original_action`,
+/// directly or through edits of its previous revisions by `from`
+fn is_edit_of(from: &AgentPubKey, update: &Update, original_action: &ActionHash) -> ExternResult<bool> {
+    let mut previous = update.original_action_address.clone();
+    for _ in 0..MAX_EDIT_CHAIN {
+        if previous == *original_action {
+            return is_original_message_by(from, original_action);
+        }
+        let Some(record) = get(previous, GetOptions::default())? else {
+            return Ok(false);
+        };
+        let Action::Update(previous_update) = record.action() else {
+            return Ok(false);
+        };
+        if previous_update.author != *from {
+            return Ok(false);
+        }
+        previous = previous_update.original_action_address.clone();
+    }
+    Ok(false)
+}
+
+/// Whether a signal received from `from` should be shown: they are a member of the conversation,
+/// and any action or receipt it carries was signed by them, for a message they authored
+pub fn authenticate_remote_signal(from: &AgentPubKey, signal: &RemoteSignal) -> ExternResult<bool> {
+    let is_authentic = match signal {
+        RemoteSignal::Message(message_record) => {
+            let action = message_record.signed_action.action();
+            message_record.original_action == *message_record.signed_action.action_address()
+                && matches!(action, Action::Create(_))
+                && is_message_entry(&message_record.message, action.entry_hash())?
+                && verify_signed_action(from, &message_record.signed_action)?
+        }
+        RemoteSignal::MessageUpdated(message_record) => {
+            let action = message_record.signed_action.action();
+            match action {
+                Action::Update(update) => {
+                    is_message_entry(&message_record.message, action.entry_hash())?
+                        && verify_signed_action(from, &message_record.signed_action)?
+                        && is_edit_of(from, update, &message_record.original_action)?
+                }
+                _ => false,
+            }
+        }
+        RemoteSignal::MessageDeleted(message_deleted) => {
+            matches!(
+                message_deleted.delete.action(),
+                Action::Delete(delete) if delete.deletes_address == message_deleted.message
+            ) && verify_signed_action(from, &message_deleted.delete)?
+                && is_original_message_by(from, &message_deleted.message)?
+        }
+        RemoteSignal::Reaction(update) => {
+            verify_signed_action(from, &update.action)? && verify_reaction_update(from, update)?
+        }
+        RemoteSignal::ReadReceipt(envelope) => verify_read_receipt(from.clone(), envelope)?,
+        RemoteSignal::DeliveryAck(_)
+        | RemoteSignal::Typing(_)
+        | RemoteSignal::Unknown => true,
+    };
+    if !is_authentic {
+        return Ok(false);
+    }
+    is_conversation_member(from)
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::freeze::filter_frozen_messages;
+use crate::message::get_latest_message;
+use crate::revocation::filter_revoked_authors;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetRepliesInput {
+    pub parent: ActionHash,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetThreadInput {
+    pub root: ActionHash,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MessagePage {
+    pub messages: Vec<MessageRecord>,
+    // Number of messages across all pages
+    pub total: usize,
+    // Whether get_thread stopped walking the thread early, so some replies may be missing
+    pub truncated: bool,
+}
+
+// Largest page returned by get_replies and get_thread
+pub const MAX_PAGE_SIZE: usize = 100;
+
+// Bounds of the walk done by get_thread, as every level costs a get_links per reply
+pub const MAX_THREAD_DEPTH: usize = 8;
+pub const MAX_THREAD_REPLIES: usize = 500;
+
+/// Get a page of the direct replies to `parent`, oldest first
+#[hdk_extern]
+pub fn get_replies(input: GetRepliesInput) -> ExternResult<MessagePage> {
+    let links = get_reply_links(input.parent)?;
+    get_message_page(links, input.offset, input.limit, false)
+}
+
+/// Get a page of the replies under `root`, oldest first, walking the thread one level at a time
+/// up to `MAX_THREAD_DEPTH` levels and `MAX_THREAD_REPLIES` replies
+#[hdk_extern]
+pub fn get_thread(input: GetThreadInput) -> ExternResult<MessagePage> {
+    let mut links: Vec<Link> = Vec::new();
+    let mut visited: HashSet<ActionHash> = HashSet::new();
+    let mut level: Vec<ActionHash> = vec![input.root];
+    let mut depth = 0;
+    while !level.is_empty() {
+        if depth == MAX_THREAD_DEPTH || links.len() >= MAX_THREAD_REPLIES {
+            return get_message_page(links, input.offset, input.limit, true);
+        }
+        let mut next_level: Vec<ActionHash> = Vec::new();
+        for parent in level {
+            if !visited.insert(parent.clone()) {
+                continue;
+            }
+            for link in get_reply_links(parent)? {
+                if let Some(reply) = link.target.clone().into_action_hash() {
+                    next_level.push(reply);
+                }
+                links.push(link);
+            }
+        }
+        level = next_level;
+        depth += 1;
+    }
+    get_message_page(links, input.offset, input.limit, false)
+}
+
+fn get_reply_links(parent: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(parent, LinkTypes::MessageToReplies)?.build())
+}
+
+fn get_message_page(
+    mut links: Vec<Link>,
+    offset: usize,
+    limit: usize,
+    truncated: bool,
+) -> ExternResult<MessagePage> {
+    links.sort_by(|link_a, link_b| {
+        link_a
+            .timestamp
+            .cmp(&link_b.timestamp)
+            .then_with(|| link_a.create_link_hash.cmp(&link_b.create_link_hash))
+    });
+    links.dedup_by(|a, b| a.target == b.target);
+    let mut results: Vec<MessageRecord> = Vec::new();
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        if let Some(r) = get_latest_message(hash)? {
+            results.push(r);
+        }
+    }
+    // Count after hiding revoked and frozen replies, so the total matches the pages
+    let results = filter_frozen_messages(filter_revoked_authors(results)?)?;
+    Ok(MessagePage {
+        total: results.len(),
+        messages: results
+            .into_iter()
+            .skip(offset)
+            .take(limit.min(MAX_PAGE_SIZE))
+            .collect(),
+        truncated,
+    })
+}
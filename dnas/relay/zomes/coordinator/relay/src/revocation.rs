@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use hdk::prelude::*;
+use relay_integrity::*;
+
+use crate::error::RelayError;
+
+use crate::delegation::is_delegation_chain_revoked;
+use crate::get_membrane_proof_for_agent;
+use crate::ownership::is_current_handover_chain;
+
+#[hdk_extern]
+pub fn revoke_membrane_proof(revocation: MembraneProofRevocation) -> ExternResult<Record> {
+    let revocation_hash = create_entry(
+        &EntryTypes::MembraneProofRevocation(revocation.clone()),
+    )?;
+    create_link(
+        revocation.for_agent.clone(),
+        revocation_hash.clone(),
+        LinkTypes::AgentToMembraneProofRevocations,
+        (),
+    )?;
+    let record = get(revocation_hash.clone(), GetOptions::default())?
+        .ok_or(
+            RelayError::not_created("MembraneProofRevocation"),
+        )?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_membrane_proof_revocations_for_agent(
+    agent: AgentPubKey,
+) -> ExternResult<Vec<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToMembraneProofRevocations)?
+            .build(),
+    )?;
+    let mut records: Vec<Record> = Vec::new();
+    for link in links {
+        let hash = ActionHash::try_from(link.target).map_err(|e| wasm_error!(e))?;
+        if let Some(record) = get(hash, GetOptions::default())? {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+/// An agent is revoked when the membrane proof they joined with, or a delegation it was signed under,
+/// was revoked before they joined, or when it was not signed under the owner at the time they joined
+#[hdk_extern]
+pub fn is_agent_revoked(agent: AgentPubKey) -> ExternResult<bool> {
+    let Some((membrane_proof, joined_at)) = get_membrane_proof_for_agent(agent.clone())? else {
+        return Ok(false);
+    };
+    if !is_current_handover_chain(membrane_proof.handovers(), joined_at)? {
+        return Ok(true);
+    }
+    if is_delegation_chain_revoked(membrane_proof.delegations(), Some(joined_at))? {
+        return Ok(true);
+    }
+    for record in get_membrane_proof_revocations_for_agent(agent)? {
+        if record.action().timestamp() >= joined_at {
+            continue;
+        }
+        let revocation: Option<MembraneProofRevocation> = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?;
+        if revocation.is_some_and(|r| r.signature == *membrane_proof.signature()) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Remembers which agents are revoked for the duration of a zome call, since every lookup goes to the network
+#[derive(Default)]
+pub struct RevocationCache {
+    revoked: HashMap<AgentPubKey, bool>,
+}
+
+impl RevocationCache {
+    pub fn is_revoked(&mut self, agent: &AgentPubKey) -> ExternResult<bool> {
+        if let Some(is_revoked) = self.revoked.get(agent) {
+            return Ok(*is_revoked);
+        }
+        let is_revoked = is_agent_revoked(agent.clone())?;
+        self.revoked.insert(agent.clone(), is_revoked);
+        Ok(is_revoked)
+    }
+}
+
+/// Hide messages authored by revoked agents
+pub fn filter_revoked_authors(records: Vec<MessageRecord>) -> ExternResult<Vec<MessageRecord>> {
+    match dna_properties()? {
+        Some(props) if props.privacy == Privacy::Private => {}
+        _ => return Ok(records),
+    }
+
+    let mut revoked = RevocationCache::default();
+    let mut results: Vec<MessageRecord> = Vec::new();
+    for record in records {
+        if !revoked.is_revoked(record.signed_action.action().author())? {
+            results.push(record);
+        }
+    }
+    Ok(results)
+}
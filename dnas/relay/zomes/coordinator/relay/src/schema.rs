@@ -0,0 +1,11 @@
+use hdk::prelude::*;
+use relay_integrity::*;
+
+/// Decode the app entry of a record, upgrading payloads written with an older schema version
+pub fn decode_versioned<T>(record: &Record) -> ExternResult<Option<T>>
+where
+    T: Versioned + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let entry: Option<T> = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+    Ok(entry.map(Versioned::upgrade))
+}
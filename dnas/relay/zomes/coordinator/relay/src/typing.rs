@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use hdk::prelude::*;
+
+use crate::freeze::check_conversation_open;
+use crate::remote_signal::{send_relay_signal, MembershipCache, RemoteSignal};
+
+// How long a typing indicator lasts unless it is renewed
+pub const TYPING_EXPIRY_MS: i64 = 1000 * 6; // 6 seconds
+// How often a sender may renew their typing indicator, matching TYPING_RATE_LIMIT_MS in the UI
+pub const TYPING_RATE_LIMIT_MS: i64 = 1000 * 2; // 2 seconds
+// How far ahead of the receiver's clock the sender's clock may be
+pub const TYPING_CLOCK_SKEW_MS: i64 = 1000 * 2; // 2 seconds
+
+thread_local! {
+    // The last typing update accepted from each sender, kept in memory as typing is never committed
+    static LAST_TYPING: RefCell<HashMap<AgentPubKey, (TypingState, Timestamp)>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TypingState {
+    Started,
+    Stopped,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotifyTypingInput {
+    pub state: TypingState,
+    // Current members of the conversation, which is the cell the zome is called in
+    pub agents: Vec<AgentPubKey>,
+}
+
+/// Sent to the other members as a remote signal, never committed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TypingUpdate {
+    pub typing: TypingState,
+    pub sent_at: Timestamp,
+}
+
+/// Tell the other members I started or stopped typing
+#[hdk_extern]
+pub fn notify_typing(input: NotifyTypingInput) -> ExternResult<()> {
+    check_conversation_open()?;
+    let me = agent_info()?.agent_initial_pubkey;
+    let mut members = MembershipCache::default();
+    let mut agents: Vec<AgentPubKey> = Vec::new();
+    for agent in input.agents {
+        if agent != me && !agents.contains(&agent) && members.is_member(&agent)? {
+            agents.push(agent);
+        }
+    }
+    let _ = send_relay_signal(
+        RemoteSignal::Typing(TypingUpdate {
+            typing: input.state,
+            sent_at: sys_time()?,
+        }),
+        agents,
+    );
+    Ok(())
+}
+
+/// Whether a typing update from `from` is within their rate limit: a start at most every
+/// TYPING_RATE_LIMIT_MS, and a stop only after a start
+fn is_within_rate_limit(from: &AgentPubKey, update: &TypingUpdate, now: Timestamp) -> bool {
+    LAST_TYPING.with(|last_typing| {
+        let mut last_typing = last_typing.borrow_mut();
+        let is_within = match (last_typing.get(from), &update.typing) {
+            (None, _) => true,
+            (Some((TypingState::Started, _)), TypingState::Stopped) => true,
+            (Some((TypingState::Stopped, _)), TypingState::Stopped) => false,
+            (Some((_, accepted_at)), TypingState::Started) => {
+                now.as_millis() - accepted_at.as_millis() >= TYPING_RATE_LIMIT_MS
+            }
+        };
+        if is_within {
+            last_typing.insert(from.clone(), (update.typing.clone(), now));
+        }
+        is_within
+    })
+}
+
+/// When a typing indicator from `from` expires, or None if it should not be shown
+/// because it is stale, claims to be sent in the future or exceeds the sender's rate limit
+pub fn accept_typing(from: &AgentPubKey, update: &TypingUpdate) -> ExternResult<Option<Timestamp>> {
+    let now = sys_time()?;
+    let age_ms = now.as_millis() - update.sent_at.as_millis();
+    if age_ms < -TYPING_CLOCK_SKEW_MS || age_ms > TYPING_EXPIRY_MS {
+        return Ok(None);
+    }
+    if !is_within_rate_limit(from, update, now) {
+        return Ok(None);
+    }
+    if update.typing == TypingState::Stopped {
+        return Ok(Some(now));
+    }
+    Ok(Some(Timestamp::from_micros(now.as_micros() + TYPING_EXPIRY_MS * 1000)))
+}
@@ -0,0 +1,202 @@
+use hdi::prelude::*;
+
+use crate::{
+    check_agent, get_agent_role, validate_conversation_open, MembraneProofKind, MembraneProofRevocation,
+    UnitEntryTypes,
+};
+
+/// Recorded by the signer of a membrane proof when its agent asks to be admitted.
+///
+/// The signer's own chain orders the admission after any revocation they made of the proof, and after the
+/// agents they admitted before with the same invite token, so validation can check that the proof was not
+/// revoked or used up before the agent was admitted.
+///
+/// Proofs signed by a delegate are admitted by that delegate, so validation only sees the revocations on the
+/// delegate's chain: revocations of the proof or its delegations made by the owner are checked by the delegate
+/// when admitting, and by `is_agent_revoked` when reading.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Admission {
+    pub agent: AgentPubKey,
+    pub membrane_proof: SerializedBytes,
+    // Latest ConversationStatus known to the author, set by the coordinator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActionHash>,
+}
+
+/// Recorded by an agent who joined a private conversation, citing their Admission.
+/// Agents need one before they can act with the role of their membrane proof.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Membership {
+    pub admission: ActionHash,
+}
+
+/// Whether the agent recorded their Membership, so that the role of their membrane proof applies
+pub fn has_membership(activity: &[RegisterAgentActivity]) -> ExternResult<bool> {
+    let membership_type: EntryType = UnitEntryTypes::Membership.try_into()?;
+    Ok(
+        activity
+            .iter()
+            .any(|a| a.action.action().entry_type() == Some(&membership_type)),
+    )
+}
+
+pub fn validate_create_admission(
+    action: EntryCreationAction,
+    admission: Admission,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = check_agent(
+        admission.agent.clone(),
+        Some(MembraneProof::new(admission.membrane_proof.clone())),
+        None,
+    )?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_conversation_open(*action.timestamp(), admission.status)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let membrane_proof = MembraneProofKind::decode(MembraneProof::new(admission.membrane_proof))?;
+    if !membrane_proof.is_signed_by(action.author().clone())? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the signer of a membrane proof can admit its agent".to_string(),
+            ),
+        );
+    }
+
+    // Former owners keep their signature on the proofs they signed, but not the authority to admit with them
+    if membrane_proof.delegations().is_empty()
+        && !get_agent_role(action.author().clone(), action.prev_action().clone())?.can_invite()
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the owner can admit the agents they invited".to_string(),
+            ),
+        );
+    }
+
+    // Revocations and uses of the proof can only come after it was signed, so the walk stops where it was issued
+    let mut filter = ChainFilter::new(action.prev_action().clone());
+    if let Some(issued_after) = membrane_proof.issued_after() {
+        filter = filter.until(issued_after);
+    }
+    let revocation_type: EntryType = UnitEntryTypes::MembraneProofRevocation.try_into()?;
+    let admission_type: EntryType = UnitEntryTypes::Admission.try_into()?;
+    let mut uses: u32 = 0;
+    for a in must_get_agent_activity(action.author().clone(), filter)? {
+        let Action::Create(create) = a.action.action() else {
+            continue;
+        };
+        if create.entry_type == revocation_type {
+            let revocation = MembraneProofRevocation::try_from(
+                must_get_entry(create.entry_hash.clone())?.content,
+            )?;
+            if revocation.signature == *membrane_proof.signature() {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Membrane proof was revoked before its agent was admitted".to_string(),
+                    ),
+                );
+            }
+        }
+        if let MembraneProofKind::InviteToken(envelope) = &membrane_proof {
+            if create.entry_type != admission_type {
+                continue;
+            }
+            let previous = Admission::try_from(
+                must_get_entry(create.entry_hash.clone())?.content,
+            )?;
+            if let MembraneProofKind::InviteToken(previous_envelope) = MembraneProofKind::decode(
+                MembraneProof::new(previous.membrane_proof),
+            )? {
+                if previous_envelope.token.key == envelope.token.key {
+                    uses += 1;
+                }
+            }
+        }
+    }
+    if let MembraneProofKind::InviteToken(envelope) = &membrane_proof {
+        if uses >= envelope.token.max_uses {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    "Invite token has reached its maximum uses".to_string(),
+                ),
+            );
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_admission(
+    _action: Update,
+    _admission: Admission,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("Admissions cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_admission(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_admission: Admission,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("Admissions cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_membership(
+    action: EntryCreationAction,
+    membership: Membership,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(membership.admission)?;
+    let admission: crate::Admission = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Membership must reference an Admission"
+                .to_string())
+            ),
+        )?;
+    if admission.agent != *action.author() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Membership must reference the Admission of its author".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_membership(
+    _action: Update,
+    _membership: Membership,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("Memberships cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_membership(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_membership: Membership,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("Memberships cannot be deleted"),
+        ),
+    )
+}
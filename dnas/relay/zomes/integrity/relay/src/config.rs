@@ -1,21 +1,65 @@
 use hdi::prelude::*;
+
+use crate::{get_agent_role, validate_conversation_open, validate_schema_version, Versioned};
+
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
 pub struct Config {
     pub title: String,
     pub image: String,
+    // Latest ConversationStatus known to the author, set by the coordinator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActionHash>,
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+impl Versioned for Config {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    fn set_schema_version(&mut self, schema_version: u32) {
+        self.schema_version = schema_version;
+    }
 }
 pub fn validate_create_config(
-    _action: EntryCreationAction,
-    _config: Config,
+    action: EntryCreationAction,
+    config: Config,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let role = get_agent_role(action.author().clone(), action.prev_action().clone())?;
+    if !role.can_configure() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only admins can change the Config".to_string(),
+            ),
+        );
+    }
+    let result = validate_conversation_open(*action.timestamp(), config.status.clone())?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    validate_schema_version(&config)
 }
 pub fn validate_update_config(
-    _action: Update,
-    _config: Config,
+    action: Update,
+    config: Config,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let role = get_agent_role(action.author.clone(), action.prev_action.clone())?;
+    if !role.can_configure() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only admins can change the Config".to_string(),
+            ),
+        );
+    }
+    let result = validate_conversation_open(action.timestamp, config.status.clone())?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    validate_schema_version(&config)
 }
 pub fn validate_delete_config(
     _action: Delete,
@@ -25,11 +69,19 @@ pub fn validate_delete_config(
     Ok(ValidateCallbackResult::Invalid(String::from("Config cannot be deleted")))
 }
 pub fn validate_create_link_config_updates(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    let role = get_agent_role(action.author.clone(), action.prev_action.clone())?;
+    if !role.can_configure() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only admins can change the Config".to_string(),
+            ),
+        );
+    }
     let path_entry_hash = Path::from("config").path_entry_hash()?;
 
     let base_hash = base_address
@@ -62,6 +114,13 @@ pub fn validate_create_link_config_updates(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ConfigUpdates links must target a Config by the same author".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_config_updates(
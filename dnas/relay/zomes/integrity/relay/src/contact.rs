@@ -1,5 +1,7 @@
 use hdi::prelude::*;
 
+use crate::{get_agent_role, validate_schema_version, Versioned};
+
 #[derive(Clone, PartialEq)]
 #[hdk_entry_helper]
 pub struct Contact {
@@ -7,6 +9,20 @@ pub struct Contact {
     pub first_name: String,
     pub last_name: String,
     pub avatar: String,
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+impl Versioned for Contact {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    fn set_schema_version(&mut self, schema_version: u32) {
+        self.schema_version = schema_version;
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -17,32 +33,54 @@ pub struct ContactRecord {
 }
 
 pub fn validate_create_contact(
-    _action: EntryCreationAction,
-    _contact: Contact,
+    action: EntryCreationAction,
+    contact: Contact,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let role = get_agent_role(action.author().clone(), action.prev_action().clone())?;
+    if !role.can_write() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only members can create contacts".to_string(),
+            ),
+        );
+    }
+    validate_schema_version(&contact)
 }
 
 pub fn validate_update_contact(
-    _action: Update,
-    _contact: Contact,
-    _original_action: EntryCreationAction,
+    action: Update,
+    contact: Contact,
+    original_action: EntryCreationAction,
     _original_contact: Contact,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    if action.author != *original_action.author() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Contact can update it".to_string(),
+            ),
+        );
+    }
+    validate_schema_version(&contact)
 }
 
 pub fn validate_delete_contact(
-    _action: Delete,
-    _original_action: EntryCreationAction,
+    action: Delete,
+    original_action: EntryCreationAction,
     _original_contact: Contact,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Contact can delete it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_create_link_contact_to_contacts(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
@@ -54,7 +92,7 @@ pub fn validate_create_link_contact_to_contacts(
             ),
         )?;
     let record = must_get_valid_record(action_hash)?;
-    let _contact: crate::Contact = record
+    let contact: crate::Contact = record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
@@ -64,21 +102,43 @@ pub fn validate_create_link_contact_to_contacts(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Contact can link to it".to_string(),
+            ),
+        );
+    }
+    if base_address != AnyLinkableHash::from(contact.public_key) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ContactToContacts links must be linked from the Contact's public key"
+                    .to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_delete_link_contact_to_contacts(
-    _action: DeleteLink,
-    _original_action: CreateLink,
+    action: DeleteLink,
+    original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a ContactToContacts link can delete it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_create_link_contact_updates(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
@@ -101,6 +161,13 @@ pub fn validate_create_link_contact_updates(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Contact can link updates to it".to_string(),
+            ),
+        );
+    }
     let action_hash = target_address
         .into_action_hash()
         .ok_or(
@@ -119,6 +186,14 @@ pub fn validate_create_link_contact_updates(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ContactUpdates links must target a Contact update by the same author"
+                    .to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
@@ -137,7 +212,7 @@ pub fn validate_delete_link_contact_updates(
 }
 
 pub fn validate_create_link_all_contacts(
-    _action: CreateLink,
+    action: CreateLink,
     _base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
@@ -161,15 +236,29 @@ pub fn validate_create_link_all_contacts(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Contact can link to it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_delete_link_all_contacts(
-    _action: DeleteLink,
-    _original_action: CreateLink,
+    action: DeleteLink,
+    original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an AllContacts link can delete it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
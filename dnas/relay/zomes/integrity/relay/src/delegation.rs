@@ -0,0 +1,314 @@
+use hdi::prelude::*;
+
+use crate::{
+    dna_properties, get_agent_role, validate_conversation_open, verify_ownership_chain,
+    OwnershipHandoverEnvelope, Properties, Role,
+};
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+pub struct InviteDelegationData {
+    pub conversation_id: String,
+    pub delegate: AgentPubKey,
+}
+
+/// Grants invitation authority to `data.delegate`, signed by the owner or by a previous delegate
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+pub struct InviteDelegationEnvelope {
+    pub signature: Signature,
+    pub data: InviteDelegationData,
+}
+
+/// Stores a delegation chain so the delegate can find it and include it in the membrane proofs they sign
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct InviteDelegation {
+    // Handovers from the progenitor to the owner who started the chain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handovers: Vec<OwnershipHandoverEnvelope>,
+    // Delegations from the owner down to the delegate, each signed by the previous delegate
+    pub chain: Vec<InviteDelegationEnvelope>,
+    // Latest ConversationStatus known to the author, set by the coordinator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActionHash>,
+}
+
+impl InviteDelegation {
+    pub fn delegate(&self) -> Option<&AgentPubKey> {
+        self.chain.last().map(|envelope| &envelope.data.delegate)
+    }
+}
+
+/// Walk a delegation chain starting at `owner`,
+/// returning the agent it grants invitation authority to, or None if it is not valid.
+pub fn verify_delegation_chain(
+    owner: AgentPubKey,
+    conversation_id: &str,
+    chain: &[InviteDelegationEnvelope],
+) -> ExternResult<Option<AgentPubKey>> {
+    let mut signer = owner;
+    for envelope in chain {
+        if envelope.data.conversation_id != conversation_id {
+            return Ok(None);
+        }
+        if !verify_signature(signer, envelope.signature.clone(), envelope.data.clone())? {
+            return Ok(None);
+        }
+        signer = envelope.data.delegate.clone();
+    }
+    Ok(Some(signer))
+}
+
+/// Delegates can invite members and guests, only the owner can invite admins
+pub fn can_delegate_role(as_role: u32) -> bool {
+    Role::from_membrane_proof_role(as_role).is_some_and(|role| role <= Role::Member)
+}
+
+/// Walk the handovers from the progenitor to an owner, then the delegations from that owner,
+/// returning the agent allowed to sign invitations, or None if either chain is not valid.
+pub fn verify_invite_authority(
+    props: &Properties,
+    conversation_id: &str,
+    handovers: &[OwnershipHandoverEnvelope],
+    delegations: &[InviteDelegationEnvelope],
+) -> ExternResult<Option<AgentPubKey>> {
+    let Some(owner) = verify_ownership_chain(props, conversation_id, handovers)? else {
+        return Ok(None);
+    };
+    verify_delegation_chain(owner, conversation_id, delegations)
+}
+
+pub fn validate_create_invite_delegation(
+    action: EntryCreationAction,
+    invite_delegation: InviteDelegation,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(props) = dna_properties()? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegations can only be created in conversations".to_string(),
+            ),
+        );
+    };
+    let Some((_, parent_chain)) = invite_delegation.chain.split_last() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegation chain cannot be empty".to_string(),
+            ),
+        );
+    };
+    let conversation_id = dna_info()?.modifiers.network_seed;
+    if verify_invite_authority(
+        &props,
+        &conversation_id,
+        &invite_delegation.handovers,
+        &invite_delegation.chain,
+    )?
+    .is_none()
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegation chain is not valid".to_string(),
+            ),
+        );
+    }
+    let signer = verify_invite_authority(
+        &props,
+        &conversation_id,
+        &invite_delegation.handovers,
+        parent_chain,
+    )?;
+    if signer.as_ref() != Some(action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegations must be created by the agent who signed the last delegation"
+                    .to_string(),
+            ),
+        );
+    }
+    validate_conversation_open(*action.timestamp(), invite_delegation.status)
+}
+
+pub fn validate_update_invite_delegation(
+    _action: Update,
+    _invite_delegation: InviteDelegation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("InviteDelegations cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_invite_delegation(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_invite_delegation: InviteDelegation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("InviteDelegations cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_agent_to_invite_delegations(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let invite_delegation: crate::InviteDelegation = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an InviteDelegation can link to it".to_string(),
+            ),
+        );
+    }
+    if invite_delegation.delegate().map(|d| AnyLinkableHash::from(d.clone()))
+        != Some(base_address)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegations must be linked from their delegate".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_invite_delegations(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("AgentToInviteDelegations links cannot be deleted"),
+        ),
+    )
+}
+
+/// Revokes a delegation, along with every delegation and invitation signed under it after the revocation.
+///
+/// Like membrane proof revocations, validation cannot see these, so they are honoured by the coordinator.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct InviteDelegationRevocation {
+    pub delegation: InviteDelegationEnvelope,
+}
+
+pub fn validate_create_invite_delegation_revocation(
+    action: EntryCreationAction,
+    revocation: InviteDelegationRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    let role = get_agent_role(action.author().clone(), action.prev_action().clone())?;
+    if role.can_invite() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let delegation = revocation.delegation;
+    if verify_signature(action.author().clone(), delegation.signature, delegation.data)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    Ok(
+        ValidateCallbackResult::Invalid(
+            "Only the owner or the signer of a delegation can revoke it".to_string(),
+        ),
+    )
+}
+
+pub fn validate_update_invite_delegation_revocation(
+    _action: Update,
+    _revocation: InviteDelegationRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("InviteDelegationRevocations cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_invite_delegation_revocation(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_revocation: InviteDelegationRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("InviteDelegationRevocations cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_agent_to_invite_delegation_revocations(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let revocation: crate::InviteDelegationRevocation = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an InviteDelegationRevocation can link to it".to_string(),
+            ),
+        );
+    }
+    if AnyLinkableHash::from(revocation.delegation.data.delegate) != base_address {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "InviteDelegationRevocations must be linked from their delegate".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_invite_delegation_revocations(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("AgentToInviteDelegationRevocations links cannot be deleted"),
+        ),
+    )
+}
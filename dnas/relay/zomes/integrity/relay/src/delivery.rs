@@ -0,0 +1,69 @@
+use hdi::prelude::*;
+
+/// Tracks sending a message to the other members as remote signals, until each of them acknowledges it.
+///
+/// Kept private in the sender's source chain, it is the outbox the coordinator retries from.
+/// Retries follow a fixed schedule from `sent_at`, and acknowledgements are recorded
+/// as `DeliveryConfirmation`s, so it is never updated.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Delivery {
+    // Original action hash of the message
+    pub message: ActionHash,
+    pub sent_at: Timestamp,
+    pub recipients: Vec<AgentPubKey>,
+}
+
+/// Records that a recipient acknowledged a message, private in the sender's source chain
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct DeliveryConfirmation {
+    // Original action hash of the message
+    pub message: ActionHash,
+    pub agent: AgentPubKey,
+    pub delivered_at: Timestamp,
+}
+
+pub fn validate_create_delivery(
+    _action: EntryCreationAction,
+    _delivery: Delivery,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_delivery(
+    _action: Update,
+    _delivery: Delivery,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_delivery(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_delivery: Delivery,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_delivery_confirmation(
+    _action: EntryCreationAction,
+    _delivery_confirmation: DeliveryConfirmation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_delivery_confirmation(
+    _action: Update,
+    _delivery_confirmation: DeliveryConfirmation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_delivery_confirmation(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_delivery_confirmation: DeliveryConfirmation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
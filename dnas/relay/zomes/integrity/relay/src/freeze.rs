@@ -0,0 +1,201 @@
+use hdi::prelude::*;
+
+use crate::{dna_properties, get_agent_role};
+
+pub const STATUS_PATH: &str = "status";
+
+/// Whether the conversation is frozen, created by the progenitor when they start the conversation.
+///
+/// The owner freezes the conversation by updating its status, which cannot be undone. Messages, config changes,
+/// delegations and admissions cite the latest status their author knows of, and validation rejects them when
+/// it is frozen. Validation cannot tell whether a newer status exists, so an agent running modified code can
+/// still cite the status from before the freeze: the coordinator hides messages timestamped after the freeze.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ConversationStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frozen: Option<String>,
+}
+
+/// Whether the end date in the DNA properties has passed at `timestamp`
+pub fn has_conversation_ended(timestamp: Timestamp) -> ExternResult<bool> {
+    let ends_at_ms = dna_properties()?.and_then(|props| props.ends_at_ms);
+    Ok(ends_at_ms.is_some_and(|ends_at_ms| timestamp.as_millis() >= ends_at_ms))
+}
+
+/// Reject actions which would change the conversation after its end date, or which cite a frozen status
+pub fn validate_conversation_open(
+    timestamp: Timestamp,
+    status: Option<ActionHash>,
+) -> ExternResult<ValidateCallbackResult> {
+    if dna_properties()?.is_none() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if has_conversation_ended(timestamp)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "The conversation has ended and is read-only".to_string(),
+            ),
+        );
+    }
+    let Some(status) = status else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Actions must cite the conversation status".to_string(),
+            ),
+        );
+    };
+    let conversation_status = must_get_conversation_status(status)?;
+    if conversation_status.frozen.is_some() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "The conversation is frozen and read-only".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn must_get_conversation_status(action_hash: ActionHash) -> ExternResult<ConversationStatus> {
+    let record = must_get_valid_record(action_hash)?;
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Action must reference a ConversationStatus"
+                .to_string())
+            ),
+        )
+}
+
+pub fn validate_create_conversation_status(
+    action: EntryCreationAction,
+    conversation_status: ConversationStatus,
+) -> ExternResult<ValidateCallbackResult> {
+    // Updates freeze the conversation, and are checked by `validate_update_conversation_status`
+    let EntryCreationAction::Create(_) = action else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let Some(props) = dna_properties()? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ConversationStatuses can only be created in conversations".to_string(),
+            ),
+        );
+    };
+    if *action.author() != props.progenitor {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the progenitor can create the ConversationStatus".to_string(),
+            ),
+        );
+    }
+    if conversation_status.frozen.is_some() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Conversations cannot be frozen when they start".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_conversation_status(
+    action: Update,
+    conversation_status: ConversationStatus,
+) -> ExternResult<ValidateCallbackResult> {
+    let role = get_agent_role(action.author.clone(), action.prev_action.clone())?;
+    if !role.can_invite() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the owner can freeze the conversation".to_string(),
+            ),
+        );
+    }
+    if conversation_status.frozen.is_none() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ConversationStatus updates must freeze the conversation".to_string(),
+            ),
+        );
+    }
+    let original_conversation_status = must_get_conversation_status(action.original_action_address)?;
+    if original_conversation_status.frozen.is_some() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "The conversation is already frozen".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_conversation_status(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_conversation_status: ConversationStatus,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("ConversationStatuses cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_conversation_status_updates(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let path_entry_hash = Path::from(STATUS_PATH).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(path_entry_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ConversationStatusUpdates links must be linked from the status path".to_string(),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _conversation_status: crate::ConversationStatus = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a ConversationStatus can link to it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_conversation_status_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("ConversationStatusUpdates links cannot be deleted"),
+        ),
+    )
+}
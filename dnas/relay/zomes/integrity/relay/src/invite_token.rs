@@ -0,0 +1,119 @@
+use hdi::prelude::*;
+
+use crate::{
+    can_delegate_role, verify_invite_authority, InviteDelegationEnvelope, OwnershipHandoverEnvelope,
+    Properties, Role,
+};
+
+/// A multi-use invitation, signed by the owner or a delegate, which anyone holding its secret key can present to join.
+///
+/// Only the public half of the key is in the token: joining agents sign their own public key with the secret
+/// key, so the membrane proofs they publish cannot be reused by anyone else. The signer counts the agents they
+/// admit with a token, and validation rejects an Admission beyond `max_uses`.
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+pub struct InviteToken {
+    pub conversation_id: String,
+    // Raw ed25519 public key of the token's secret key, which also identifies the token
+    pub key: Vec<u8>,
+    pub max_uses: u32,
+    pub expires_at: Timestamp,
+    pub as_role: u32,
+    // Head of the signer's source chain when they signed, where validation stops counting uses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_after: Option<ActionHash>,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes)]
+pub struct InviteTokenEnvelope {
+    pub signature: Signature,
+    pub token: InviteToken,
+    // Signature of the joining agent's public key with the token's secret key, added when joining
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_signature: Option<Signature>,
+    // Handover chain from the progenitor to the owner, empty while the progenitor owns the conversation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handovers: Vec<OwnershipHandoverEnvelope>,
+    // Delegation chain from the owner to the signer, empty when signed by the owner
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegations: Vec<InviteDelegationEnvelope>,
+}
+
+pub fn check_invite_token(
+    props: Properties,
+    conversation_id: &str,
+    agent_pub_key: AgentPubKey,
+    envelope: InviteTokenEnvelope,
+    joined_at: Option<Timestamp>,
+) -> ExternResult<ValidateCallbackResult> {
+    if envelope.token.conversation_id != conversation_id {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "invite token is not for this conversation".to_string(),
+            ),
+        );
+    }
+    if joined_at.is_some_and(|joined_at| joined_at > envelope.token.expires_at) {
+        return Ok(
+            ValidateCallbackResult::Invalid("invite token has expired".to_string()),
+        );
+    }
+    if Role::from_membrane_proof_role(envelope.token.as_role).is_none() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "invite token role is not valid".to_string(),
+            ),
+        );
+    }
+    if envelope.token.key.len() != 32 {
+        return Ok(
+            ValidateCallbackResult::Invalid("invite token key is not valid".to_string()),
+        );
+    }
+    let Some(agent_signature) = envelope.agent_signature else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "invite token must be signed for the joining agent".to_string(),
+            ),
+        );
+    };
+    if !verify_signature_raw(
+        AgentPubKey::from_raw_32(envelope.token.key.clone()),
+        agent_signature,
+        agent_pub_key.get_raw_39().to_vec(),
+    )? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "invite token was not signed for this agent".to_string(),
+            ),
+        );
+    }
+    if !envelope.delegations.is_empty() && !can_delegate_role(envelope.token.as_role) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "delegated invite tokens cannot grant this role".to_string(),
+            ),
+        );
+    }
+    // Only the owner can invite, or the agents they delegated invitation authority to.
+    // Tokens signed by former owners cannot be admitted by them, see `validate_create_admission`.
+    let Some(signer) = verify_invite_authority(
+        &props,
+        conversation_id,
+        &envelope.handovers,
+        &envelope.delegations,
+    )? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "invite token handover or delegation chain invalid".to_string(),
+            ),
+        );
+    };
+    if verify_signature(signer, envelope.signature, envelope.token)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    Ok(
+        ValidateCallbackResult::Invalid(
+            "invite token signature invalid".to_string(),
+        ),
+    )
+}
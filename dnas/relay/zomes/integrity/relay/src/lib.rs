@@ -4,6 +4,30 @@ pub mod message;
 pub use message::*;
 pub mod config;
 pub use config::*;
+pub mod role;
+pub use role::*;
+pub mod limits;
+pub use limits::*;
+pub mod revocation;
+pub use revocation::*;
+pub mod admission;
+pub use admission::*;
+pub mod invite_token;
+pub use invite_token::*;
+pub mod delegation;
+pub use delegation::*;
+pub mod ownership;
+pub use ownership::*;
+pub mod freeze;
+pub use freeze::*;
+pub mod reaction;
+pub use reaction::*;
+pub mod read;
+pub use read::*;
+pub mod delivery;
+pub use delivery::*;
+pub mod schema;
+pub use schema::*;
 use hdi::prelude::*;
 
 pub const MESSAGES_PATH_PREFIX: &str = "msg";
@@ -12,6 +36,30 @@ pub fn messages_path(bucket: u32) -> Path {
     Path::from(format!("{}.{}", MESSAGES_PATH_PREFIX, bucket))
 }
 
+// Timestamp range of messages contained within a single bucket, when not set in the DNA properties
+pub const DEFAULT_BUCKET_RANGE_MS: u64 = 1000 * 60 * 60 * 24; // 1 day
+
+// How far a Message bucket may lag behind its action timestamp,
+// to allow for the time between computing the bucket and committing the Message
+pub const BUCKET_TOLERANCE_MS: i64 = 1000 * 60; // 1 minute
+
+fn default_bucket_range_ms() -> u64 {
+    DEFAULT_BUCKET_RANGE_MS
+}
+
+/// Bucket containing messages created at `timestamp`.
+///
+/// This must match `getBucket` in the UI, which rounds buckets to the nearest range.
+pub fn bucket_for_timestamp(timestamp: Timestamp) -> ExternResult<u32> {
+    let (created_ms, bucket_range_ms) = match dna_properties()? {
+        Some(props) => (props.created_ms(), props.bucket_range_ms as i64),
+        None => (0, DEFAULT_BUCKET_RANGE_MS as i64),
+    };
+    let elapsed_ms = timestamp.as_millis() - created_ms;
+    let bucket = (2 * elapsed_ms + bucket_range_ms).div_euclid(2 * bucket_range_ms);
+    Ok(bucket.clamp(0, u32::MAX as i64) as u32)
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[hdk_entry_types]
@@ -20,6 +68,21 @@ pub enum EntryTypes {
     Config(Config),
     Message(Message),
     Contact(Contact),
+    MembraneProofRevocation(MembraneProofRevocation),
+    Admission(Admission),
+    Membership(Membership),
+    InviteDelegation(InviteDelegation),
+    OwnershipHandover(OwnershipHandover),
+    OwnershipAcceptance(OwnershipAcceptance),
+    OwnershipReclaim(OwnershipReclaim),
+    InviteDelegationRevocation(InviteDelegationRevocation),
+    ConversationStatus(ConversationStatus),
+    #[entry_type(visibility = "private")]
+    ReadMarker(ReadMarker),
+    #[entry_type(visibility = "private")]
+    Delivery(Delivery),
+    #[entry_type(visibility = "private")]
+    DeliveryConfirmation(DeliveryConfirmation),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,19 +94,129 @@ pub enum LinkTypes {
     ContactToContacts,
     ContactUpdates,
     AllContacts,
+    AgentToMembraneProofRevocations,
+    AgentToInviteDelegations,
+    AgentToOwnershipHandovers,
+    OwnershipAcceptances,
+    AgentToInviteDelegationRevocations,
+    ConversationStatusUpdates,
+    MessageToReplies,
+    MessageToReactions,
+    ReadReceipts,
 }
 
-#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
 pub struct MembraneProofData {
     pub conversation_id: String,
     pub for_agent: AgentPubKey,
     pub as_role: u32,
+    // Optional validity window, checked against the timestamp of the agent joining.
+    // Skipped when not set, so proofs signed without a window keep the same signed bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<Timestamp>,
+    // Head of the signer's source chain when they signed, where validation stops looking for revocations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_after: Option<ActionHash>,
 }
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes)]
 pub struct MembraneProofEnvelope {
     pub signature: Signature,
     pub data: MembraneProofData,
+    // Handover chain from the progenitor to the owner, empty while the progenitor owns the conversation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handovers: Vec<OwnershipHandoverEnvelope>,
+    // Delegation chain from the owner to the signer, empty when signed by the owner
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegations: Vec<InviteDelegationEnvelope>,
+}
+
+/// The kinds of membrane proof an agent can join a private conversation with
+#[derive(Debug)]
+pub enum MembraneProofKind {
+    // Signed for a single agent
+    Agent(MembraneProofEnvelope),
+    // Signed for anyone holding the token
+    InviteToken(InviteTokenEnvelope),
+}
+
+impl MembraneProofKind {
+    pub fn decode(membrane_proof: MembraneProof) -> ExternResult<Self> {
+        let bytes = (*membrane_proof).clone();
+        if let Ok(envelope) = MembraneProofEnvelope::try_from(bytes.clone()) {
+            return Ok(MembraneProofKind::Agent(envelope));
+        }
+        let envelope = InviteTokenEnvelope::try_from(bytes).map_err(|e| wasm_error!(e))?;
+        Ok(MembraneProofKind::InviteToken(envelope))
+    }
+
+    pub fn as_role(&self) -> u32 {
+        match self {
+            MembraneProofKind::Agent(envelope) => envelope.data.as_role,
+            MembraneProofKind::InviteToken(envelope) => envelope.token.as_role,
+        }
+    }
+
+    /// Signature of the proof's signer, which identifies the proof in revocations
+    pub fn signature(&self) -> &Signature {
+        match self {
+            MembraneProofKind::Agent(envelope) => &envelope.signature,
+            MembraneProofKind::InviteToken(envelope) => &envelope.signature,
+        }
+    }
+
+    /// Handover chain from the progenitor to the owner the proof was signed under
+    pub fn handovers(&self) -> &[OwnershipHandoverEnvelope] {
+        match self {
+            MembraneProofKind::Agent(envelope) => &envelope.handovers,
+            MembraneProofKind::InviteToken(envelope) => &envelope.handovers,
+        }
+    }
+
+    /// Delegation chain from the owner to the signer of the proof
+    pub fn delegations(&self) -> &[InviteDelegationEnvelope] {
+        match self {
+            MembraneProofKind::Agent(envelope) => &envelope.delegations,
+            MembraneProofKind::InviteToken(envelope) => &envelope.delegations,
+        }
+    }
+
+    /// Head of the signer's source chain when they signed the proof
+    pub fn issued_after(&self) -> Option<ActionHash> {
+        match self {
+            MembraneProofKind::Agent(envelope) => envelope.data.issued_after.clone(),
+            MembraneProofKind::InviteToken(envelope) => envelope.token.issued_after.clone(),
+        }
+    }
+
+    pub fn is_signed_by(&self, agent: AgentPubKey) -> ExternResult<bool> {
+        match self {
+            MembraneProofKind::Agent(envelope) => {
+                verify_signature(agent, envelope.signature.clone(), envelope.data.clone())
+            }
+            MembraneProofKind::InviteToken(envelope) => {
+                verify_signature(agent, envelope.signature.clone(), envelope.token.clone())
+            }
+        }
+    }
+}
+
+/// The membrane proof in the `AgentValidationPkg` of an agent's activity
+pub fn membrane_proof_from_activity(
+    activity: &[RegisterAgentActivity],
+) -> ExternResult<Option<MembraneProofKind>> {
+    let membrane_proof = activity
+        .iter()
+        .find_map(|a| match a.action.action() {
+            Action::AgentValidationPkg(AgentValidationPkg { membrane_proof, .. }) => {
+                Some(membrane_proof.clone())
+            }
+            _ => None,
+        })
+        .flatten();
+    membrane_proof.map(MembraneProofKind::decode).transpose()
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes, PartialEq)]
@@ -54,21 +227,58 @@ pub enum Privacy {
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 pub struct Properties {
+    // Milliseconds since the epoch: the UI sets it from `Date.getTime()` despite the Timestamp type
     pub created: Timestamp,
     pub privacy: Privacy,
     pub progenitor: AgentPubKey,
+    #[serde(default)]
+    pub limits: Limits,
+    #[serde(default = "default_bucket_range_ms")]
+    pub bucket_range_ms: u64,
+    // Optional end date, in milliseconds since the epoch, after which the conversation is read-only
+    #[serde(default)]
+    pub ends_at_ms: Option<i64>,
+}
+
+impl Properties {
+    /// When the conversation was created, in milliseconds since the epoch.
+    ///
+    /// `created` holds milliseconds, so its raw value is read rather than converted with `as_millis()`.
+    pub fn created_ms(&self) -> i64 {
+        self.created.as_micros()
+    }
+}
+
+/// Properties of a conversation cell, or None for the provisioned cell which is installed without properties
+pub fn dna_properties() -> ExternResult<Option<Properties>> {
+    let info = dna_info()?;
+    if info.modifiers.properties.bytes().len() == 1 {
+        return Ok(None);
+    }
+    let props = Properties::try_from(info.modifiers.properties)
+        .map_err(|e| wasm_error!(e))?;
+    Ok(Some(props))
 }
 
+/// `joined_at` is the timestamp of the agent's `CreateAgent` action, not known during genesis self-check
 pub fn check_agent(
     agent_pub_key: AgentPubKey,
     membrane_proof: Option<MembraneProof>,
+    joined_at: Option<Timestamp>,
 ) -> ExternResult<ValidateCallbackResult> {
     let info = dna_info()?;
-    if info.modifiers.properties.bytes().len() == 1 {
+    let Some(props) = dna_properties()? else {
         return Ok(ValidateCallbackResult::Valid);
+    };
+    if let Some(joined_at) = joined_at {
+        if agent_pub_key != props.progenitor && has_conversation_ended(joined_at)? {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    "conversation has ended".to_string(),
+                ),
+            );
+        }
     }
-    let props = Properties::try_from(info.modifiers.properties)
-        .map_err(|e| wasm_error!(e))?;
     if props.privacy == Privacy::Public {
         return Ok(ValidateCallbackResult::Valid);
     }
@@ -84,8 +294,18 @@ pub fn check_agent(
             )
         }
         Some(serialized_proof) => {
-            let envelope = MembraneProofEnvelope::try_from((*serialized_proof).clone())
-                .map_err(|e| wasm_error!(e))?;
+            let envelope = match MembraneProofKind::decode(serialized_proof)? {
+                MembraneProofKind::Agent(envelope) => envelope,
+                MembraneProofKind::InviteToken(envelope) => {
+                    return check_invite_token(
+                        props,
+                        &info.modifiers.network_seed,
+                        agent_pub_key,
+                        envelope,
+                        joined_at,
+                    );
+                }
+            };
             if envelope.data.conversation_id != info.modifiers.network_seed {
                 return Ok(
                     ValidateCallbackResult::Invalid(
@@ -100,7 +320,53 @@ pub fn check_agent(
                     ),
                 );
             }
-            if verify_signature(props.progenitor, envelope.signature, envelope.data)? {
+            if let Some(joined_at) = joined_at {
+                if envelope.data.valid_from.is_some_and(|valid_from| joined_at < valid_from) {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            "membrane proof is not valid yet".to_string(),
+                        ),
+                    );
+                }
+                if envelope.data.valid_until.is_some_and(|valid_until| joined_at > valid_until)
+                {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            "membrane proof has expired".to_string(),
+                        ),
+                    );
+                }
+            }
+            if Role::from_membrane_proof_role(envelope.data.as_role).is_none() {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "membrane proof role is not valid".to_string(),
+                    ),
+                );
+            }
+            if !envelope.delegations.is_empty() && !can_delegate_role(envelope.data.as_role) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "delegated membrane proofs cannot grant this role".to_string(),
+                    ),
+                );
+            }
+            // Only the owner can invite, or the agents they delegated invitation authority to.
+            // Validation cannot see whether the handover chain was superseded when the agent joined,
+            // so former owners cannot admit the agents they invited, see `validate_create_admission`.
+            let Some(signer) = verify_invite_authority(
+                &props,
+                &info.modifiers.network_seed,
+                &envelope.handovers,
+                &envelope.delegations,
+            )? else {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "membrane proof handover or delegation chain invalid".to_string(),
+                    ),
+                );
+            };
+            if verify_signature(signer, envelope.signature, envelope.data)? {
                 return Ok(ValidateCallbackResult::Valid);
             }
             Ok(
@@ -116,14 +382,15 @@ pub fn check_agent(
 pub fn genesis_self_check(
     data: GenesisSelfCheckData,
 ) -> ExternResult<ValidateCallbackResult> {
-    check_agent(data.agent_key, data.membrane_proof)
+    check_agent(data.agent_key, data.membrane_proof, None)
 }
 
 pub fn validate_agent_joining(
     agent_pub_key: AgentPubKey,
     membrane_proof: &Option<MembraneProof>,
+    joined_at: Timestamp,
 ) -> ExternResult<ValidateCallbackResult> {
-    check_agent(agent_pub_key, (*membrane_proof).clone())
+    check_agent(agent_pub_key, (*membrane_proof).clone(), Some(joined_at))
 }
 
 #[hdk_extern]
@@ -139,6 +406,78 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 config,
                             )
                         }
+                        EntryTypes::OwnershipReclaim(ownership_reclaim) => {
+                            validate_create_ownership_reclaim(
+                                EntryCreationAction::Create(action),
+                                ownership_reclaim,
+                            )
+                        }
+                        EntryTypes::ReadMarker(read_marker) => {
+                            validate_create_read_marker(
+                                EntryCreationAction::Create(action),
+                                read_marker,
+                            )
+                        }
+                        EntryTypes::Delivery(delivery) => {
+                            validate_create_delivery(
+                                EntryCreationAction::Create(action),
+                                delivery,
+                            )
+                        }
+                        EntryTypes::DeliveryConfirmation(delivery_confirmation) => {
+                            validate_create_delivery_confirmation(
+                                EntryCreationAction::Create(action),
+                                delivery_confirmation,
+                            )
+                        }
+                        EntryTypes::ConversationStatus(conversation_status) => {
+                            validate_create_conversation_status(
+                                EntryCreationAction::Create(action),
+                                conversation_status,
+                            )
+                        }
+                        EntryTypes::OwnershipAcceptance(ownership_acceptance) => {
+                            validate_create_ownership_acceptance(
+                                EntryCreationAction::Create(action),
+                                ownership_acceptance,
+                            )
+                        }
+                        EntryTypes::OwnershipHandover(ownership_handover) => {
+                            validate_create_ownership_handover(
+                                EntryCreationAction::Create(action),
+                                ownership_handover,
+                            )
+                        }
+                        EntryTypes::InviteDelegation(invite_delegation) => {
+                            validate_create_invite_delegation(
+                                EntryCreationAction::Create(action),
+                                invite_delegation,
+                            )
+                        }
+                        EntryTypes::InviteDelegationRevocation(invite_delegation_revocation) => {
+                            validate_create_invite_delegation_revocation(
+                                EntryCreationAction::Create(action),
+                                invite_delegation_revocation,
+                            )
+                        }
+                        EntryTypes::MembraneProofRevocation(membrane_proof_revocation) => {
+                            validate_create_membrane_proof_revocation(
+                                EntryCreationAction::Create(action),
+                                membrane_proof_revocation,
+                            )
+                        }
+                        EntryTypes::Admission(admission) => {
+                            validate_create_admission(
+                                EntryCreationAction::Create(action),
+                                admission,
+                            )
+                        }
+                        EntryTypes::Membership(membership) => {
+                            validate_create_membership(
+                                EntryCreationAction::Create(action),
+                                membership,
+                            )
+                        }
                         EntryTypes::Message(message) => {
                             validate_create_message(
                                 EntryCreationAction::Create(action),
@@ -161,6 +500,78 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 config,
                             )
                         }
+                        EntryTypes::OwnershipReclaim(ownership_reclaim) => {
+                            validate_create_ownership_reclaim(
+                                EntryCreationAction::Update(action),
+                                ownership_reclaim,
+                            )
+                        }
+                        EntryTypes::ReadMarker(read_marker) => {
+                            validate_create_read_marker(
+                                EntryCreationAction::Update(action),
+                                read_marker,
+                            )
+                        }
+                        EntryTypes::Delivery(delivery) => {
+                            validate_create_delivery(
+                                EntryCreationAction::Update(action),
+                                delivery,
+                            )
+                        }
+                        EntryTypes::DeliveryConfirmation(delivery_confirmation) => {
+                            validate_create_delivery_confirmation(
+                                EntryCreationAction::Update(action),
+                                delivery_confirmation,
+                            )
+                        }
+                        EntryTypes::ConversationStatus(conversation_status) => {
+                            validate_create_conversation_status(
+                                EntryCreationAction::Update(action),
+                                conversation_status,
+                            )
+                        }
+                        EntryTypes::OwnershipAcceptance(ownership_acceptance) => {
+                            validate_create_ownership_acceptance(
+                                EntryCreationAction::Update(action),
+                                ownership_acceptance,
+                            )
+                        }
+                        EntryTypes::OwnershipHandover(ownership_handover) => {
+                            validate_create_ownership_handover(
+                                EntryCreationAction::Update(action),
+                                ownership_handover,
+                            )
+                        }
+                        EntryTypes::InviteDelegation(invite_delegation) => {
+                            validate_create_invite_delegation(
+                                EntryCreationAction::Update(action),
+                                invite_delegation,
+                            )
+                        }
+                        EntryTypes::InviteDelegationRevocation(invite_delegation_revocation) => {
+                            validate_create_invite_delegation_revocation(
+                                EntryCreationAction::Update(action),
+                                invite_delegation_revocation,
+                            )
+                        }
+                        EntryTypes::MembraneProofRevocation(membrane_proof_revocation) => {
+                            validate_create_membrane_proof_revocation(
+                                EntryCreationAction::Update(action),
+                                membrane_proof_revocation,
+                            )
+                        }
+                        EntryTypes::Admission(admission) => {
+                            validate_create_admission(
+                                EntryCreationAction::Update(action),
+                                admission,
+                            )
+                        }
+                        EntryTypes::Membership(membership) => {
+                            validate_create_membership(
+                                EntryCreationAction::Update(action),
+                                membership,
+                            )
+                        }
                         EntryTypes::Message(message) => {
                             validate_create_message(
                                 EntryCreationAction::Update(action),
@@ -225,11 +636,67 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             )
                         }
                         EntryTypes::Message(message) => {
-                            validate_update_message(action, message)
+                            let original_app_entry = must_get_valid_record(
+                                action.clone().original_action_address,
+                            )?;
+                            let original_message = match Message::try_from(
+                                original_app_entry,
+                            ) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(
+                                        ValidateCallbackResult::Invalid(
+                                            format!("Expected to get Message from Record: {e:?}"),
+                                        ),
+                                    );
+                                }
+                            };
+                            validate_update_message(
+                                action,
+                                message,
+                                original_create_action,
+                                original_message,
+                            )
                         }
                         EntryTypes::Config(config) => {
                             validate_update_config(action, config)
                         }
+                        EntryTypes::OwnershipReclaim(ownership_reclaim) => {
+                            validate_update_ownership_reclaim(action, ownership_reclaim)
+                        }
+                        EntryTypes::ReadMarker(read_marker) => {
+                            validate_update_read_marker(action, read_marker)
+                        }
+                        EntryTypes::Delivery(delivery) => {
+                            validate_update_delivery(action, delivery)
+                        }
+                        EntryTypes::DeliveryConfirmation(delivery_confirmation) => {
+                            validate_update_delivery_confirmation(action, delivery_confirmation)
+                        }
+                        EntryTypes::ConversationStatus(conversation_status) => {
+                            validate_update_conversation_status(action, conversation_status)
+                        }
+                        EntryTypes::OwnershipAcceptance(ownership_acceptance) => {
+                            validate_update_ownership_acceptance(action, ownership_acceptance)
+                        }
+                        EntryTypes::OwnershipHandover(ownership_handover) => {
+                            validate_update_ownership_handover(action, ownership_handover)
+                        }
+                        EntryTypes::InviteDelegation(invite_delegation) => {
+                            validate_update_invite_delegation(action, invite_delegation)
+                        }
+                        EntryTypes::InviteDelegationRevocation(invite_delegation_revocation) => {
+                            validate_update_invite_delegation_revocation(action, invite_delegation_revocation)
+                        }
+                        EntryTypes::MembraneProofRevocation(membrane_proof_revocation) => {
+                            validate_update_membrane_proof_revocation(action, membrane_proof_revocation)
+                        }
+                        EntryTypes::Admission(admission) => {
+                            validate_update_admission(action, admission)
+                        }
+                        EntryTypes::Membership(membership) => {
+                            validate_update_membership(action, membership)
+                        }
                         _ => {
                             Ok(
                                 ValidateCallbackResult::Invalid(
@@ -315,6 +782,90 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         ),
                     );
                 }
+                EntryTypes::OwnershipReclaim(original_ownership_reclaim) => {
+                    validate_delete_ownership_reclaim(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_ownership_reclaim,
+                    )
+                }
+                EntryTypes::ReadMarker(original_read_marker) => {
+                    validate_delete_read_marker(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_read_marker,
+                    )
+                }
+                EntryTypes::Delivery(original_delivery) => {
+                    validate_delete_delivery(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_delivery,
+                    )
+                }
+                EntryTypes::DeliveryConfirmation(original_delivery_confirmation) => {
+                    validate_delete_delivery_confirmation(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_delivery_confirmation,
+                    )
+                }
+                EntryTypes::ConversationStatus(original_conversation_status) => {
+                    validate_delete_conversation_status(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_conversation_status,
+                    )
+                }
+                EntryTypes::OwnershipAcceptance(original_ownership_acceptance) => {
+                    validate_delete_ownership_acceptance(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_ownership_acceptance,
+                    )
+                }
+                EntryTypes::OwnershipHandover(original_ownership_handover) => {
+                    validate_delete_ownership_handover(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_ownership_handover,
+                    )
+                }
+                EntryTypes::InviteDelegation(original_invite_delegation) => {
+                    validate_delete_invite_delegation(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_invite_delegation,
+                    )
+                }
+                EntryTypes::InviteDelegationRevocation(original_invite_delegation_revocation) => {
+                    validate_delete_invite_delegation_revocation(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_invite_delegation_revocation,
+                    )
+                }
+                EntryTypes::MembraneProofRevocation(original_membrane_proof_revocation) => {
+                    validate_delete_membrane_proof_revocation(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_membrane_proof_revocation,
+                    )
+                }
+                EntryTypes::Admission(original_admission) => {
+                    validate_delete_admission(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_admission,
+                    )
+                }
+                EntryTypes::Membership(original_membership) => {
+                    validate_delete_membership(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_membership,
+                    )
+                }
             }
         }
         FlatOp::RegisterCreateLink {
@@ -373,55 +924,127 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         tag,
                     )
                 }
-            }
-        }
-        FlatOp::RegisterDeleteLink {
-            link_type,
-            base_address,
-            target_address,
-            tag,
-            original_action,
-            action,
-        } => {
-            match link_type {
-                LinkTypes::ConfigUpdates => {
-                    validate_delete_link_config_updates(
+                LinkTypes::ReadReceipts => {
+                    validate_create_link_read_receipts(
                         action,
-                        original_action,
                         base_address,
                         target_address,
                         tag,
                     )
                 }
-                LinkTypes::MessageUpdates => {
-                    validate_delete_link_message_updates(
+                LinkTypes::MessageToReactions => {
+                    validate_create_link_message_to_reactions(
                         action,
-                        original_action,
                         base_address,
                         target_address,
                         tag,
                     )
                 }
-                LinkTypes::AllMessages => {
-                    validate_delete_link_all_messages(
+                LinkTypes::MessageToReplies => {
+                    validate_create_link_message_to_replies(
                         action,
-                        original_action,
                         base_address,
                         target_address,
                         tag,
                     )
                 }
-                LinkTypes::ContactToContacts => {
-                    validate_delete_link_contact_to_contacts(
+                LinkTypes::ConversationStatusUpdates => {
+                    validate_create_link_conversation_status_updates(
                         action,
-                        original_action,
                         base_address,
                         target_address,
                         tag,
                     )
                 }
-                LinkTypes::ContactUpdates => {
-                    validate_delete_link_contact_updates(
+                LinkTypes::OwnershipAcceptances => {
+                    validate_create_link_ownership_acceptances(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToOwnershipHandovers => {
+                    validate_create_link_agent_to_ownership_handovers(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToInviteDelegations => {
+                    validate_create_link_agent_to_invite_delegations(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToInviteDelegationRevocations => {
+                    validate_create_link_agent_to_invite_delegation_revocations(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToMembraneProofRevocations => {
+                    validate_create_link_agent_to_membrane_proof_revocations(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+            }
+        }
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => {
+            match link_type {
+                LinkTypes::ConfigUpdates => {
+                    validate_delete_link_config_updates(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::MessageUpdates => {
+                    validate_delete_link_message_updates(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AllMessages => {
+                    validate_delete_link_all_messages(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::ContactToContacts => {
+                    validate_delete_link_contact_to_contacts(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::ContactUpdates => {
+                    validate_delete_link_contact_updates(
                         action,
                         original_action,
                         base_address,
@@ -438,6 +1061,87 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         tag,
                     )
                 }
+                LinkTypes::ReadReceipts => {
+                    validate_delete_link_read_receipts(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::MessageToReactions => {
+                    validate_delete_link_message_to_reactions(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::MessageToReplies => {
+                    validate_delete_link_message_to_replies(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::ConversationStatusUpdates => {
+                    validate_delete_link_conversation_status_updates(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::OwnershipAcceptances => {
+                    validate_delete_link_ownership_acceptances(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToOwnershipHandovers => {
+                    validate_delete_link_agent_to_ownership_handovers(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToInviteDelegations => {
+                    validate_delete_link_agent_to_invite_delegations(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToInviteDelegationRevocations => {
+                    validate_delete_link_agent_to_invite_delegation_revocations(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::AgentToMembraneProofRevocations => {
+                    validate_delete_link_agent_to_membrane_proof_revocations(
+                        action,
+                        original_action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
             }
         }
         FlatOp::StoreRecord(store_record) => {
@@ -450,53 +1154,437 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 config,
                             )
                         }
+                        EntryTypes::OwnershipReclaim(ownership_reclaim) => {
+                            validate_create_ownership_reclaim(
+                                EntryCreationAction::Create(action),
+                                ownership_reclaim,
+                            )
+                        }
+                        EntryTypes::ReadMarker(read_marker) => {
+                            validate_create_read_marker(
+                                EntryCreationAction::Create(action),
+                                read_marker,
+                            )
+                        }
+                        EntryTypes::Delivery(delivery) => {
+                            validate_create_delivery(
+                                EntryCreationAction::Create(action),
+                                delivery,
+                            )
+                        }
+                        EntryTypes::DeliveryConfirmation(delivery_confirmation) => {
+                            validate_create_delivery_confirmation(
+                                EntryCreationAction::Create(action),
+                                delivery_confirmation,
+                            )
+                        }
+                        EntryTypes::ConversationStatus(conversation_status) => {
+                            validate_create_conversation_status(
+                                EntryCreationAction::Create(action),
+                                conversation_status,
+                            )
+                        }
+                        EntryTypes::OwnershipAcceptance(ownership_acceptance) => {
+                            validate_create_ownership_acceptance(
+                                EntryCreationAction::Create(action),
+                                ownership_acceptance,
+                            )
+                        }
+                        EntryTypes::OwnershipHandover(ownership_handover) => {
+                            validate_create_ownership_handover(
+                                EntryCreationAction::Create(action),
+                                ownership_handover,
+                            )
+                        }
+                        EntryTypes::InviteDelegation(invite_delegation) => {
+                            validate_create_invite_delegation(
+                                EntryCreationAction::Create(action),
+                                invite_delegation,
+                            )
+                        }
+                        EntryTypes::InviteDelegationRevocation(invite_delegation_revocation) => {
+                            validate_create_invite_delegation_revocation(
+                                EntryCreationAction::Create(action),
+                                invite_delegation_revocation,
+                            )
+                        }
+                        EntryTypes::MembraneProofRevocation(membrane_proof_revocation) => {
+                            validate_create_membrane_proof_revocation(
+                                EntryCreationAction::Create(action),
+                                membrane_proof_revocation,
+                            )
+                        }
+                        EntryTypes::Admission(admission) => {
+                            validate_create_admission(
+                                EntryCreationAction::Create(action),
+                                admission,
+                            )
+                        }
+                        EntryTypes::Membership(membership) => {
+                            validate_create_membership(
+                                EntryCreationAction::Create(action),
+                                membership,
+                            )
+                        }
                         EntryTypes::Message(message) => {
                             validate_create_message(
                                 EntryCreationAction::Create(action),
                                 message,
                             )
                         }
-                        EntryTypes::Contact(contact) => {
-                            validate_create_contact(
-                                EntryCreationAction::Create(action),
-                                contact,
-                            )
+                        EntryTypes::Contact(contact) => {
+                            validate_create_contact(
+                                EntryCreationAction::Create(action),
+                                contact,
+                            )
+                        }
+                    }
+                }
+                OpRecord::UpdateEntry {
+                    original_action_hash,
+                    app_entry,
+                    action,
+                    ..
+                } => {
+                    let original_record = must_get_valid_record(original_action_hash)?;
+                    let original_action = original_record.action().clone();
+                    let original_action = match original_action {
+                        Action::Create(create) => EntryCreationAction::Create(create),
+                        Action::Update(update) => EntryCreationAction::Update(update),
+                        _ => {
+                            return Ok(
+                                ValidateCallbackResult::Invalid(
+                                    "Original action for an update must be a Create or Update action"
+                                        .to_string(),
+                                ),
+                            );
+                        }
+                    };
+                    match app_entry {
+                        EntryTypes::Config(config) => {
+                            let result = validate_create_config(
+                                EntryCreationAction::Update(action.clone()),
+                                config.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_config: Option<Config> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_config = match original_config {
+                                    Some(config) => config,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_config(action, config)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::OwnershipReclaim(ownership_reclaim) => {
+                            let result = validate_create_ownership_reclaim(
+                                EntryCreationAction::Update(action.clone()),
+                                ownership_reclaim.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_ownership_reclaim: Option<OwnershipReclaim> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_ownership_reclaim = match original_ownership_reclaim {
+                                    Some(ownership_reclaim) => ownership_reclaim,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_ownership_reclaim(action, ownership_reclaim)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::ReadMarker(read_marker) => {
+                            let result = validate_create_read_marker(
+                                EntryCreationAction::Update(action.clone()),
+                                read_marker.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_read_marker: Option<ReadMarker> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_read_marker = match original_read_marker {
+                                    Some(read_marker) => read_marker,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_read_marker(action, read_marker)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::Delivery(delivery) => {
+                            let result = validate_create_delivery(
+                                EntryCreationAction::Update(action.clone()),
+                                delivery.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_delivery: Option<Delivery> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_delivery = match original_delivery {
+                                    Some(delivery) => delivery,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_delivery(action, delivery)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::DeliveryConfirmation(delivery_confirmation) => {
+                            let result = validate_create_delivery_confirmation(
+                                EntryCreationAction::Update(action.clone()),
+                                delivery_confirmation.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_delivery_confirmation: Option<DeliveryConfirmation> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_delivery_confirmation = match original_delivery_confirmation {
+                                    Some(delivery_confirmation) => delivery_confirmation,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_delivery_confirmation(action, delivery_confirmation)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::ConversationStatus(conversation_status) => {
+                            let result = validate_create_conversation_status(
+                                EntryCreationAction::Update(action.clone()),
+                                conversation_status.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_conversation_status: Option<ConversationStatus> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_conversation_status = match original_conversation_status {
+                                    Some(conversation_status) => conversation_status,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_conversation_status(action, conversation_status)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::OwnershipAcceptance(ownership_acceptance) => {
+                            let result = validate_create_ownership_acceptance(
+                                EntryCreationAction::Update(action.clone()),
+                                ownership_acceptance.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_ownership_acceptance: Option<OwnershipAcceptance> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_ownership_acceptance = match original_ownership_acceptance {
+                                    Some(ownership_acceptance) => ownership_acceptance,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_ownership_acceptance(action, ownership_acceptance)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::OwnershipHandover(ownership_handover) => {
+                            let result = validate_create_ownership_handover(
+                                EntryCreationAction::Update(action.clone()),
+                                ownership_handover.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_ownership_handover: Option<OwnershipHandover> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_ownership_handover = match original_ownership_handover {
+                                    Some(ownership_handover) => ownership_handover,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_ownership_handover(action, ownership_handover)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::InviteDelegation(invite_delegation) => {
+                            let result = validate_create_invite_delegation(
+                                EntryCreationAction::Update(action.clone()),
+                                invite_delegation.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_invite_delegation: Option<InviteDelegation> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_invite_delegation = match original_invite_delegation {
+                                    Some(invite_delegation) => invite_delegation,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_invite_delegation(action, invite_delegation)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::InviteDelegationRevocation(invite_delegation_revocation) => {
+                            let result = validate_create_invite_delegation_revocation(
+                                EntryCreationAction::Update(action.clone()),
+                                invite_delegation_revocation.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_invite_delegation_revocation: Option<InviteDelegationRevocation> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_invite_delegation_revocation = match original_invite_delegation_revocation {
+                                    Some(invite_delegation_revocation) => invite_delegation_revocation,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_invite_delegation_revocation(action, invite_delegation_revocation)
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::MembraneProofRevocation(membrane_proof_revocation) => {
+                            let result = validate_create_membrane_proof_revocation(
+                                EntryCreationAction::Update(action.clone()),
+                                membrane_proof_revocation.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_membrane_proof_revocation: Option<MembraneProofRevocation> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_membrane_proof_revocation = match original_membrane_proof_revocation {
+                                    Some(membrane_proof_revocation) => membrane_proof_revocation,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_membrane_proof_revocation(action, membrane_proof_revocation)
+                            } else {
+                                Ok(result)
+                            }
                         }
-                    }
-                }
-                OpRecord::UpdateEntry {
-                    original_action_hash,
-                    app_entry,
-                    action,
-                    ..
-                } => {
-                    let original_record = must_get_valid_record(original_action_hash)?;
-                    let original_action = original_record.action().clone();
-                    let original_action = match original_action {
-                        Action::Create(create) => EntryCreationAction::Create(create),
-                        Action::Update(update) => EntryCreationAction::Update(update),
-                        _ => {
-                            return Ok(
-                                ValidateCallbackResult::Invalid(
-                                    "Original action for an update must be a Create or Update action"
-                                        .to_string(),
-                                ),
-                            );
+                        EntryTypes::Admission(admission) => {
+                            let result = validate_create_admission(
+                                EntryCreationAction::Update(action.clone()),
+                                admission.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_admission: Option<Admission> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let _original_admission = match original_admission {
+                                    Some(admission) => admission,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_admission(action, admission)
+                            } else {
+                                Ok(result)
+                            }
                         }
-                    };
-                    match app_entry {
-                        EntryTypes::Config(config) => {
-                            let result = validate_create_config(
+                        EntryTypes::Membership(membership) => {
+                            let result = validate_create_membership(
                                 EntryCreationAction::Update(action.clone()),
-                                config.clone(),
+                                membership.clone(),
                             )?;
                             if let ValidateCallbackResult::Valid = result {
-                                let original_config: Option<Config> = original_record
+                                let original_membership: Option<Membership> = original_record
                                     .entry()
                                     .to_app_option()
                                     .map_err(|e| wasm_error!(e))?;
-                                let _original_config = match original_config {
-                                    Some(config) => config,
+                                let _original_membership = match original_membership {
+                                    Some(membership) => membership,
                                     None => {
                                         return Ok(
                                             ValidateCallbackResult::Invalid(
@@ -506,7 +1594,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                         );
                                     }
                                 };
-                                validate_update_config(action, config)
+                                validate_update_membership(action, membership)
                             } else {
                                 Ok(result)
                             }
@@ -521,7 +1609,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                     .entry()
                                     .to_app_option()
                                     .map_err(|e| wasm_error!(e))?;
-                                let _original_message = match original_message {
+                                let original_message = match original_message {
                                     Some(message) => message,
                                     None => {
                                         return Ok(
@@ -532,7 +1620,12 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                         );
                                     }
                                 };
-                                validate_update_message(action, message)
+                                validate_update_message(
+                                    action,
+                                    message,
+                                    original_action,
+                                    original_message,
+                                )
                             } else {
                                 Ok(result)
                             }
@@ -629,6 +1722,90 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 original_config,
                             )
                         }
+                        EntryTypes::OwnershipReclaim(original_ownership_reclaim) => {
+                            validate_delete_ownership_reclaim(
+                                action,
+                                original_action,
+                                original_ownership_reclaim,
+                            )
+                        }
+                        EntryTypes::ReadMarker(original_read_marker) => {
+                            validate_delete_read_marker(
+                                action,
+                                original_action,
+                                original_read_marker,
+                            )
+                        }
+                        EntryTypes::Delivery(original_delivery) => {
+                            validate_delete_delivery(
+                                action,
+                                original_action,
+                                original_delivery,
+                            )
+                        }
+                        EntryTypes::DeliveryConfirmation(original_delivery_confirmation) => {
+                            validate_delete_delivery_confirmation(
+                                action,
+                                original_action,
+                                original_delivery_confirmation,
+                            )
+                        }
+                        EntryTypes::ConversationStatus(original_conversation_status) => {
+                            validate_delete_conversation_status(
+                                action,
+                                original_action,
+                                original_conversation_status,
+                            )
+                        }
+                        EntryTypes::OwnershipAcceptance(original_ownership_acceptance) => {
+                            validate_delete_ownership_acceptance(
+                                action,
+                                original_action,
+                                original_ownership_acceptance,
+                            )
+                        }
+                        EntryTypes::OwnershipHandover(original_ownership_handover) => {
+                            validate_delete_ownership_handover(
+                                action,
+                                original_action,
+                                original_ownership_handover,
+                            )
+                        }
+                        EntryTypes::InviteDelegation(original_invite_delegation) => {
+                            validate_delete_invite_delegation(
+                                action,
+                                original_action,
+                                original_invite_delegation,
+                            )
+                        }
+                        EntryTypes::InviteDelegationRevocation(original_invite_delegation_revocation) => {
+                            validate_delete_invite_delegation_revocation(
+                                action,
+                                original_action,
+                                original_invite_delegation_revocation,
+                            )
+                        }
+                        EntryTypes::MembraneProofRevocation(original_membrane_proof_revocation) => {
+                            validate_delete_membrane_proof_revocation(
+                                action,
+                                original_action,
+                                original_membrane_proof_revocation,
+                            )
+                        }
+                        EntryTypes::Admission(original_admission) => {
+                            validate_delete_admission(
+                                action,
+                                original_action,
+                                original_admission,
+                            )
+                        }
+                        EntryTypes::Membership(original_membership) => {
+                            validate_delete_membership(
+                                action,
+                                original_action,
+                                original_membership,
+                            )
+                        }
                         EntryTypes::Message(original_message) => {
                             validate_delete_message(
                                 action,
@@ -701,6 +1878,78 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 tag,
                             )
                         }
+                        LinkTypes::ReadReceipts => {
+                            validate_create_link_read_receipts(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::MessageToReactions => {
+                            validate_create_link_message_to_reactions(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::MessageToReplies => {
+                            validate_create_link_message_to_replies(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::ConversationStatusUpdates => {
+                            validate_create_link_conversation_status_updates(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::OwnershipAcceptances => {
+                            validate_create_link_ownership_acceptances(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::AgentToOwnershipHandovers => {
+                            validate_create_link_agent_to_ownership_handovers(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::AgentToInviteDelegations => {
+                            validate_create_link_agent_to_invite_delegations(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::AgentToInviteDelegationRevocations => {
+                            validate_create_link_agent_to_invite_delegation_revocations(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
+                        LinkTypes::AgentToMembraneProofRevocations => {
+                            validate_create_link_agent_to_membrane_proof_revocations(
+                                action,
+                                base_address,
+                                target_address,
+                                tag,
+                            )
+                        }
                     }
                 }
                 OpRecord::DeleteLink { original_action_hash, base_address, action } => {
@@ -780,6 +2029,87 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 create_link.tag,
                             )
                         }
+                        LinkTypes::ReadReceipts => {
+                            validate_delete_link_read_receipts(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::MessageToReactions => {
+                            validate_delete_link_message_to_reactions(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::MessageToReplies => {
+                            validate_delete_link_message_to_replies(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::ConversationStatusUpdates => {
+                            validate_delete_link_conversation_status_updates(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::OwnershipAcceptances => {
+                            validate_delete_link_ownership_acceptances(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToOwnershipHandovers => {
+                            validate_delete_link_agent_to_ownership_handovers(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToInviteDelegations => {
+                            validate_delete_link_agent_to_invite_delegations(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToInviteDelegationRevocations => {
+                            validate_delete_link_agent_to_invite_delegation_revocations(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToMembraneProofRevocations => {
+                            validate_delete_link_agent_to_membrane_proof_revocations(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
                     }
                 }
                 OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
@@ -802,7 +2132,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     match previous_action.action() {
                         Action::AgentValidationPkg(
                             AgentValidationPkg { membrane_proof, .. },
-                        ) => validate_agent_joining(agent, membrane_proof),
+                        ) => validate_agent_joining(agent, membrane_proof, action.timestamp),
                         _ => {
                             Ok(
                                 ValidateCallbackResult::Invalid(
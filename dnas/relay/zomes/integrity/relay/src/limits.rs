@@ -0,0 +1,60 @@
+use hdi::prelude::*;
+
+use crate::dna_properties;
+
+/// Limits on Message content and attachments, set per conversation in the DNA `Properties`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Limits {
+    // Maximum length of Message.content, in bytes
+    pub max_content_length: usize,
+    // Maximum number of files attached to a single Message
+    pub max_attachments: usize,
+    // Maximum declared size of a single attached file, in bytes
+    pub max_attachment_size: usize,
+    // MIME types allowed for attached files, either exact ("image/png") or wildcard ("image/*").
+    // Any type is allowed when empty.
+    pub allowed_file_types: Vec<String>,
+    // Rate limit on posting Messages, for every author below admin
+    pub slow_mode: Option<SlowMode>,
+}
+
+/// Each author may post at most `burst` Messages within any `min_interval_ms`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SlowMode {
+    pub min_interval_ms: u64,
+    pub burst: u32,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_content_length: 10_000,
+            max_attachments: 10,
+            max_attachment_size: 15 * 1024 * 1024,
+            allowed_file_types: vec![],
+            slow_mode: None,
+        }
+    }
+}
+
+impl Limits {
+    pub fn is_file_type_allowed(&self, file_type: &str) -> bool {
+        if self.allowed_file_types.is_empty() {
+            return true;
+        }
+        self.allowed_file_types.iter().any(|allowed| {
+            match allowed.strip_suffix("/*") {
+                Some(prefix) => file_type
+                    .split_once('/')
+                    .map_or(false, |(t, _)| t.eq_ignore_ascii_case(prefix)),
+                None => allowed.eq_ignore_ascii_case(file_type),
+            }
+        })
+    }
+}
+
+/// Limits of this conversation, or the defaults when the DNA has no properties
+pub fn dna_limits() -> ExternResult<Limits> {
+    Ok(dna_properties()?.map(|props| props.limits).unwrap_or_default())
+}
@@ -1,5 +1,11 @@
 use hdi::prelude::*;
 
+use crate::{
+    agent_role_from_activity, bucket_for_timestamp, dna_limits, get_agent_role, messages_path,
+    validate_conversation_open, validate_schema_version, Role, UnitEntryTypes, Versioned,
+    BUCKET_TOLERANCE_MS,
+};
+
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
 pub struct File {
     pub name: String,
@@ -9,12 +15,44 @@ pub struct File {
     pub storage_entry_hash: EntryHash,
 }
 
+/// Mirror of the `FileMetadata` entry of the file_storage zome, which `File.storage_entry_hash` points to
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct FileMetadata {
+    pub name: String,
+    pub last_modified: Timestamp,
+    pub size: usize,
+    pub file_type: String,
+    pub chunks_hashes: Vec<EntryHash>,
+}
+
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
 pub struct Message {
     pub content: String,
     pub bucket: u32,
     pub images: Vec<File>,
+    // Latest ConversationStatus known to the author, set by the coordinator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActionHash>,
+    // Original action hash of the Message this one replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<ActionHash>,
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+impl Versioned for Message {
+    // Version 2 added reply_to, which decodes as None from older payloads
+    const SCHEMA_VERSION: u32 = 2;
+
+    fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    fn set_schema_version(&mut self, schema_version: u32) {
+        self.schema_version = schema_version;
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -24,38 +62,316 @@ pub struct MessageRecord {
     pub message: Option<Message>,
 }
 pub fn validate_create_message(
-    _action: EntryCreationAction,
-    _message: Message,
+    action: EntryCreationAction,
+    message: Message,
+) -> ExternResult<ValidateCallbackResult> {
+    // The role and the slow mode both need the author's activity, so it is fetched once for both
+    let activity = must_get_agent_activity(
+        action.author().clone(),
+        ChainFilter::new(action.prev_action().clone()),
+    )?;
+    let role = agent_role_from_activity(action.author(), &activity)?;
+    if !role.can_write() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only members can post messages".to_string(),
+            ),
+        );
+    }
+    let result = validate_conversation_open(*action.timestamp(), message.status.clone())?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_schema_version(&message)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_message_bucket(&action, &message)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_message_reply(&action, &message)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_message_limits(&message)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    let result = validate_message_rate(&action, role, &activity)?;
+    if !matches!(result, ValidateCallbackResult::Valid) {
+        return Ok(result);
+    }
+    validate_message_attachments(&message)
+}
+/// A reply must reference an original Message, and cannot be moved to another parent by an update
+fn validate_message_reply(
+    action: &EntryCreationAction,
+    message: &Message,
 ) -> ExternResult<ValidateCallbackResult> {
+    match action {
+        EntryCreationAction::Create(_) => {
+            let Some(parent) = message.reply_to.clone() else {
+                return Ok(ValidateCallbackResult::Valid);
+            };
+            let parent_record = must_get_valid_record(parent)?;
+            let parent_message: Option<crate::Message> = parent_record
+                .entry()
+                .to_app_option()
+                .map_err(|e| wasm_error!(e))?;
+            if parent_message.is_none() {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Messages can only reply to a Message".to_string(),
+                    ),
+                );
+            }
+            if !matches!(parent_record.action(), Action::Create(_)) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Replies must reference the original Message".to_string(),
+                    ),
+                );
+            }
+        }
+        EntryCreationAction::Update(update) => {
+            let original_record = must_get_valid_record(
+                update.original_action_address.clone(),
+            )?;
+            let original_message: crate::Message = original_record
+                .entry()
+                .to_app_option()
+                .map_err(|e| wasm_error!(e))?
+                .ok_or(
+                    wasm_error!(
+                        WasmErrorInner::Guest("Updated action must reference an entry"
+                        .to_string())
+                    ),
+                )?;
+            if message.reply_to != original_message.reply_to {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Message reply_to cannot be changed by an update".to_string(),
+                    ),
+                );
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+fn validate_message_bucket(
+    action: &EntryCreationAction,
+    message: &Message,
+) -> ExternResult<ValidateCallbackResult> {
+    match action {
+        EntryCreationAction::Create(create) => {
+            let latest_bucket = bucket_for_timestamp(create.timestamp)?;
+            let earliest_bucket = bucket_for_timestamp(
+                Timestamp::from_micros(
+                    create.timestamp.as_micros() - BUCKET_TOLERANCE_MS * 1000,
+                ),
+            )?;
+            if message.bucket < earliest_bucket || message.bucket > latest_bucket {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        format!(
+                            "Message bucket {} does not match its timestamp, expected {}",
+                            message.bucket,
+                            latest_bucket
+                        ),
+                    ),
+                );
+            }
+        }
+        EntryCreationAction::Update(update) => {
+            let original_record = must_get_valid_record(
+                update.original_action_address.clone(),
+            )?;
+            let original_message: crate::Message = original_record
+                .entry()
+                .to_app_option()
+                .map_err(|e| wasm_error!(e))?
+                .ok_or(
+                    wasm_error!(
+                        WasmErrorInner::Guest("Updated action must reference an entry"
+                        .to_string())
+                    ),
+                )?;
+            if message.bucket != original_message.bucket {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Message bucket cannot be changed by an update".to_string(),
+                    ),
+                );
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+fn validate_message_limits(message: &Message) -> ExternResult<ValidateCallbackResult> {
+    let limits = dna_limits()?;
+    if message.content.len() > limits.max_content_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Message content must be at most {} bytes",
+                    limits.max_content_length
+                ),
+            ),
+        );
+    }
+    if message.images.len() > limits.max_attachments {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Message can have at most {} attachments", limits.max_attachments),
+            ),
+        );
+    }
+    for file in message.images.iter() {
+        if file.size > limits.max_attachment_size {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Attachment {} must be at most {} bytes",
+                        file.name,
+                        limits.max_attachment_size
+                    ),
+                ),
+            );
+        }
+        if !limits.is_file_type_allowed(&file.file_type) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Attachment type {} is not allowed", file.file_type),
+                ),
+            );
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+/// Enforce slow mode, counting the Messages the author created within `min_interval_ms` before this one
+fn validate_message_rate(
+    action: &EntryCreationAction,
+    role: Role,
+    activity: &[RegisterAgentActivity],
+) -> ExternResult<ValidateCallbackResult> {
+    let EntryCreationAction::Create(create) = action else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let Some(slow_mode) = dna_limits()?.slow_mode else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    if role.can_moderate() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let message_type: EntryType = UnitEntryTypes::Message.try_into()?;
+    let window_start = create.timestamp.as_micros() - slow_mode.min_interval_ms as i64 * 1000;
+    // Timestamps grow along the chain, so the walk back from the newest action stops at the window start
+    let mut newest_first: Vec<&Action> = activity.iter().map(|a| a.action.action()).collect();
+    newest_first.sort_by_key(|previous| std::cmp::Reverse(previous.action_seq()));
+    let recent_messages = newest_first
+        .into_iter()
+        .take_while(|previous| previous.timestamp().as_micros() > window_start)
+        .filter(|previous| matches!(previous, Action::Create(c) if c.entry_type == message_type))
+        .take(slow_mode.burst as usize)
+        .count();
+    if recent_messages >= slow_mode.burst as usize {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Slow mode allows at most {} messages every {} ms",
+                    slow_mode.burst,
+                    slow_mode.min_interval_ms
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+/// Attachments must point to file metadata stored by the file_storage zome, with the same size and type
+fn validate_message_attachments(message: &Message) -> ExternResult<ValidateCallbackResult> {
+    for file in &message.images {
+        let entry = must_get_entry(file.storage_entry_hash.clone())?.content;
+        let Ok(file_metadata) = FileMetadata::try_from(entry) else {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Attachment {} does not reference stored file metadata", file.name),
+                ),
+            );
+        };
+        if file_metadata.size != file.size {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Attachment {} has size {} but its stored file has size {}",
+                        file.name,
+                        file.size,
+                        file_metadata.size
+                    ),
+                ),
+            );
+        }
+        if file_metadata.file_type != file.file_type {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Attachment {} has type {} but its stored file has type {}",
+                        file.name,
+                        file.file_type,
+                        file_metadata.file_type
+                    ),
+                ),
+            );
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_update_message(
-    _action: Update,
+    action: Update,
     _message: Message,
+    original_action: EntryCreationAction,
+    _original_message: Message,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Message can update it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_message(
-    _action: Delete,
-    _original_action: EntryCreationAction,
+    action: Delete,
+    original_action: EntryCreationAction,
     _original_message: Message,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author()
+        && !get_agent_role(action.author.clone(), action.prev_action.clone())?
+            .can_moderate()
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Message or a moderator can delete it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_create_link_message_updates(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    let action_hash = base_address
+    let original_message_hash = base_address
         .into_action_hash()
         .ok_or(
             wasm_error!(
                 WasmErrorInner::Guest("No action hash associated with link".to_string())
             ),
         )?;
-    let record = must_get_valid_record(action_hash)?;
+    let record = must_get_valid_record(original_message_hash.clone())?;
     let _message: crate::Message = record
         .entry()
         .to_app_option()
@@ -66,6 +382,20 @@ pub fn validate_create_link_message_updates(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Message can link updates to it".to_string(),
+            ),
+        );
+    }
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageUpdates links must be linked from the original Message".to_string(),
+            ),
+        );
+    }
     let action_hash = target_address
         .into_action_hash()
         .ok_or(
@@ -84,6 +414,40 @@ pub fn validate_create_link_message_updates(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageUpdates links must target a Message update by the same author"
+                    .to_string(),
+            ),
+        );
+    }
+    let Action::Update(update) = record.action() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageUpdates links must target an Update".to_string(),
+            ),
+        );
+    };
+    // Follow the edit chain back to the Message it started from
+    let mut previous_action_hash = update.original_action_address.clone();
+    loop {
+        let previous_record = must_get_valid_record(previous_action_hash.clone())?;
+        match previous_record.action() {
+            Action::Update(update) => {
+                previous_action_hash = update.original_action_address.clone();
+            }
+            _ => break,
+        }
+    }
+    if previous_action_hash != original_message_hash {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageUpdates links must target an update of the Message they are linked from"
+                    .to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_message_updates(
@@ -100,11 +464,19 @@ pub fn validate_delete_link_message_updates(
     )
 }
 pub fn validate_create_link_all_messages(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    let role = get_agent_role(action.author.clone(), action.prev_action.clone())?;
+    if !role.can_write() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only members can post messages".to_string(),
+            ),
+        );
+    }
     // Check the entry type for the given action hash
     let action_hash = target_address
         .into_action_hash()
@@ -114,7 +486,7 @@ pub fn validate_create_link_all_messages(
             ),
         )?;
     let record = must_get_valid_record(action_hash)?;
-    let _message: crate::Message = record
+    let message: crate::Message = record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
@@ -124,14 +496,94 @@ pub fn validate_create_link_all_messages(
                 .to_string())
             ),
         )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a Message can link to it".to_string(),
+            ),
+        );
+    }
+    let path_entry_hash = messages_path(message.bucket).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(path_entry_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "AllMessages links must be linked from the path of their Message's bucket"
+                    .to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_all_messages(
-    _action: DeleteLink,
-    _original_action: CreateLink,
+    action: DeleteLink,
+    original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an AllMessages link can delete it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_link_message_to_replies(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let message: crate::Message = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a reply can link to it".to_string(),
+            ),
+        );
+    }
+    if message.reply_to.map(AnyLinkableHash::from) != Some(base_address) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageToReplies links must be linked from the Message the reply is to"
+                    .to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_message_to_replies(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a MessageToReplies link can delete it".to_string(),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
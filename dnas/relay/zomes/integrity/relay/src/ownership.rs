@@ -0,0 +1,528 @@
+use hdi::prelude::*;
+
+use crate::{dna_properties, get_agent_role, Properties, UnitEntryTypes};
+
+pub const OWNERSHIP_PATH: &str = "ownership";
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+pub struct OwnershipHandoverData {
+    pub conversation_id: String,
+    pub successor: AgentPubKey,
+    // The successor must accept before this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Hands ownership over to `data.successor`, signed by the owner at the time
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+pub struct OwnershipHandoverEnvelope {
+    pub signature: Signature,
+    pub data: OwnershipHandoverData,
+}
+
+/// Proposes a successor as the owner of the conversation.
+///
+/// Proposing gives up ownership straight away, so the proposer is an admin from then on. The successor
+/// becomes the owner when they accept with an `OwnershipAcceptance` before the handover expires. The proposer
+/// can cancel by deleting the handover, and takes ownership back with an `OwnershipReclaim` once it expired
+/// without being accepted.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct OwnershipHandover {
+    // Handovers from the progenitor down to the successor, each signed by the previous owner
+    pub chain: Vec<OwnershipHandoverEnvelope>,
+}
+
+impl OwnershipHandover {
+    pub fn successor(&self) -> Option<&AgentPubKey> {
+        self.chain.last().map(|envelope| &envelope.data.successor)
+    }
+}
+
+/// Accepts a handover, citing the head of the proposer's source chain so validation can check that the
+/// handover was not cancelled before. A cancellation after that head is not seen, so the coordinator only
+/// cancels handovers which have not been accepted yet.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct OwnershipAcceptance {
+    pub handover_hash: ActionHash,
+    pub proposer_chain_top: ActionHash,
+}
+
+/// Takes ownership back after a handover expired, citing an action the successor made after it expired.
+///
+/// Acceptances must come before the handover expires, so the successor's source chain up to that action
+/// shows that the handover was never accepted.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct OwnershipReclaim {
+    pub handover_hash: ActionHash,
+    pub successor_chain_top: ActionHash,
+}
+
+/// Walk a handover chain starting at the progenitor,
+/// returning the agent it hands ownership to, or None if it is not valid.
+///
+/// Proofs and delegations carry the chain from the progenitor to the owner who signed them,
+/// so they can be checked without looking up the current owner.
+pub fn verify_ownership_chain(
+    props: &Properties,
+    conversation_id: &str,
+    chain: &[OwnershipHandoverEnvelope],
+) -> ExternResult<Option<AgentPubKey>> {
+    let mut owner = props.progenitor.clone();
+    for envelope in chain {
+        if envelope.data.conversation_id != conversation_id {
+            return Ok(None);
+        }
+        if !verify_signature(owner, envelope.signature.clone(), envelope.data.clone())? {
+            return Ok(None);
+        }
+        owner = envelope.data.successor.clone();
+    }
+    Ok(Some(owner))
+}
+
+/// The latest entry of the given type created in a source chain
+fn latest_created(activity: &[RegisterAgentActivity], entry_type: &EntryType) -> Option<ActionHash> {
+    activity
+        .iter()
+        .filter_map(|a| match a.action.action() {
+            Action::Create(create) if create.entry_type == *entry_type => {
+                Some((create.action_seq, a.action.action_address().clone()))
+            }
+            _ => None,
+        })
+        .max_by_key(|(action_seq, _)| *action_seq)
+        .map(|(_, action_hash)| action_hash)
+}
+
+/// Whether an agent owns the conversation according to the handovers, acceptances and reclaims in their
+/// source chain, or None if they never took part in a handover
+pub fn ownership_from_activity(activity: &[RegisterAgentActivity]) -> ExternResult<Option<bool>> {
+    Ok(ownership_state(activity)?.0)
+}
+
+/// Proposing a handover gives up ownership until it is reclaimed, accepting one takes ownership.
+/// Returns whether the agent is the owner, and the handover they proposed which is still pending.
+fn ownership_state(
+    activity: &[RegisterAgentActivity],
+) -> ExternResult<(Option<bool>, Option<ActionHash>)> {
+    let handover_type: EntryType = UnitEntryTypes::OwnershipHandover.try_into()?;
+    let acceptance_type: EntryType = UnitEntryTypes::OwnershipAcceptance.try_into()?;
+    let reclaim_type: EntryType = UnitEntryTypes::OwnershipReclaim.try_into()?;
+    let mut creates: Vec<(&Create, &ActionHash)> = activity
+        .iter()
+        .filter_map(|a| match a.action.action() {
+            Action::Create(create) => Some((create, a.action.action_address())),
+            _ => None,
+        })
+        .collect();
+    creates.sort_by_key(|(create, _)| create.action_seq);
+
+    let mut is_owner = None;
+    let mut pending_handover = None;
+    for (create, action_hash) in creates {
+        if create.entry_type == handover_type {
+            is_owner = Some(false);
+            pending_handover = Some(action_hash.clone());
+        } else if create.entry_type == acceptance_type || create.entry_type == reclaim_type {
+            is_owner = Some(true);
+            pending_handover = None;
+        }
+    }
+    Ok((is_owner, pending_handover))
+}
+
+fn must_get_ownership_handover(action_hash: ActionHash) -> ExternResult<OwnershipHandover> {
+    let record = must_get_valid_record(action_hash)?;
+    if let Some(ownership_acceptance) = record
+        .entry()
+        .to_app_option::<OwnershipAcceptance>()
+        .map_err(|e| wasm_error!(e))?
+    {
+        return must_get_ownership_handover(ownership_acceptance.handover_hash);
+    }
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Action must reference an OwnershipHandover"
+                .to_string())
+            ),
+        )
+}
+
+pub fn validate_create_ownership_handover(
+    action: EntryCreationAction,
+    ownership_handover: OwnershipHandover,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(props) = dna_properties()? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandovers can only be created in conversations".to_string(),
+            ),
+        );
+    };
+    let Some((_, parent_chain)) = ownership_handover.chain.split_last() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandover chain cannot be empty".to_string(),
+            ),
+        );
+    };
+    let conversation_id = dna_info()?.modifiers.network_seed;
+    if verify_ownership_chain(&props, &conversation_id, &ownership_handover.chain)?.is_none() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandover chain is not valid".to_string(),
+            ),
+        );
+    }
+    let owner = verify_ownership_chain(&props, &conversation_id, parent_chain)?;
+    if owner.as_ref() != Some(action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandovers must be created by the agent who signed the last handover"
+                    .to_string(),
+            ),
+        );
+    }
+    let role = get_agent_role(action.author().clone(), action.prev_action().clone())?;
+    if !role.can_invite() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the owner can hand over ownership".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_ownership_handover(
+    _action: Update,
+    _ownership_handover: OwnershipHandover,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipHandovers cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_ownership_handover(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_ownership_handover: OwnershipHandover,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an OwnershipHandover can cancel it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_ownership_acceptance(
+    action: EntryCreationAction,
+    ownership_acceptance: OwnershipAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    let ownership_handover = must_get_ownership_handover(ownership_acceptance.handover_hash.clone())?;
+    if ownership_handover.successor() != Some(action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the successor can accept an OwnershipHandover".to_string(),
+            ),
+        );
+    }
+    let expires_at = ownership_handover.chain.last().and_then(|envelope| envelope.data.expires_at);
+    if expires_at.is_some_and(|expires_at| *action.timestamp() > expires_at) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandover has expired".to_string(),
+            ),
+        );
+    }
+    // The proposer cancels by deleting the handover, which must not have happened before the cited chain head
+    let proposer = must_get_valid_record(ownership_acceptance.handover_hash.clone())?
+        .action()
+        .author()
+        .clone();
+    let proposer_activity = must_get_agent_activity(
+        proposer,
+        ChainFilter::new(ownership_acceptance.proposer_chain_top)
+            .until(ownership_acceptance.handover_hash.clone()),
+    )?;
+    if !proposer_activity
+        .iter()
+        .any(|a| *a.action.action_address() == ownership_acceptance.handover_hash)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipAcceptance must cite a proposer chain head after the handover".to_string(),
+            ),
+        );
+    }
+    if proposer_activity.iter().any(|a| {
+        matches!(
+            a.action.action(), Action::Delete(delete)
+            if delete.deletes_address == ownership_acceptance.handover_hash
+        )
+    }) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandover was cancelled".to_string(),
+            ),
+        );
+    }
+    // Handover chains only grow, so accepting a handover older than the author's last one would revive a stale ownership
+    let activity = must_get_agent_activity(
+        action.author().clone(),
+        ChainFilter::new(action.prev_action().clone()),
+    )?;
+    let acceptance_type: EntryType = UnitEntryTypes::OwnershipAcceptance.try_into()?;
+    if let Some(previous_hash) = latest_created(&activity, &acceptance_type) {
+        let previous_handover = must_get_ownership_handover(previous_hash)?;
+        if ownership_handover.chain.len() <= previous_handover.chain.len() {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    "OwnershipAcceptances must accept a later handover than the author's last one"
+                        .to_string(),
+                ),
+            );
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_ownership_acceptance(
+    _action: Update,
+    _ownership_acceptance: OwnershipAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipAcceptances cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_ownership_acceptance(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_ownership_acceptance: OwnershipAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipAcceptances cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_ownership_reclaim(
+    action: EntryCreationAction,
+    ownership_reclaim: OwnershipReclaim,
+) -> ExternResult<ValidateCallbackResult> {
+    let activity = must_get_agent_activity(
+        action.author().clone(),
+        ChainFilter::new(action.prev_action().clone()),
+    )?;
+    let (_, pending_handover) = ownership_state(&activity)?;
+    if pending_handover.as_ref() != Some(&ownership_reclaim.handover_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipReclaims must reference the author's pending handover".to_string(),
+            ),
+        );
+    }
+    let ownership_handover = must_get_ownership_handover(ownership_reclaim.handover_hash.clone())?;
+    let Some(successor) = ownership_handover.successor() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandover chain cannot be empty".to_string(),
+            ),
+        );
+    };
+    let Some(expires_at) = ownership_handover.chain.last().and_then(|envelope| envelope.data.expires_at)
+    else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandovers without an expiry cannot be reclaimed".to_string(),
+            ),
+        );
+    };
+    let successor_chain_top = must_get_action(ownership_reclaim.successor_chain_top.clone())?;
+    if successor_chain_top.action().author() != successor
+        || successor_chain_top.action().timestamp() <= expires_at
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipReclaims must cite an action of the successor made after the handover expired"
+                    .to_string(),
+            ),
+        );
+    }
+    let acceptance_type: EntryType = UnitEntryTypes::OwnershipAcceptance.try_into()?;
+    let successor_activity = must_get_agent_activity(
+        successor.clone(),
+        ChainFilter::new(ownership_reclaim.successor_chain_top),
+    )?;
+    for a in successor_activity {
+        let Action::Create(create) = a.action.action() else {
+            continue;
+        };
+        if create.entry_type != acceptance_type {
+            continue;
+        }
+        let ownership_acceptance = OwnershipAcceptance::try_from(
+            must_get_entry(create.entry_hash.clone())?.content,
+        )?;
+        if ownership_acceptance.handover_hash == ownership_reclaim.handover_hash {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    "OwnershipHandover was accepted".to_string(),
+                ),
+            );
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_ownership_reclaim(
+    _action: Update,
+    _ownership_reclaim: OwnershipReclaim,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipReclaims cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_ownership_reclaim(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_ownership_reclaim: OwnershipReclaim,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipReclaims cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_agent_to_ownership_handovers(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let ownership_handover: crate::OwnershipHandover = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an OwnershipHandover can link to it".to_string(),
+            ),
+        );
+    }
+    if ownership_handover.successor().map(|s| AnyLinkableHash::from(s.clone()))
+        != Some(base_address)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipHandovers must be linked from their successor".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_ownership_handovers(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("AgentToOwnershipHandovers links cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_ownership_acceptances(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let path_entry_hash = Path::from(OWNERSHIP_PATH).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(path_entry_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "OwnershipAcceptances links must be linked from the ownership path".to_string(),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _ownership_acceptance: crate::OwnershipAcceptance = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of an OwnershipAcceptance can link to it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_ownership_acceptances(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("OwnershipAcceptances links cannot be deleted"),
+        ),
+    )
+}
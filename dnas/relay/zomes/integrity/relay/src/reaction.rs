@@ -0,0 +1,179 @@
+use hdi::prelude::*;
+
+use crate::{agent_role_from_activity, has_conversation_ended, LinkTypes};
+
+// Long enough for emoji built from several code points, like flags and skin tones
+pub const MAX_REACTION_BYTES: usize = 64;
+
+/// The emoji stored in the tag of a MessageToReactions link, or None if the tag is not one
+pub fn reaction_from_tag(tag: &LinkTag) -> Option<String> {
+    let emoji = String::from_utf8(tag.0.clone()).ok()?;
+    if emoji.len() > MAX_REACTION_BYTES || !is_emoji(&emoji) {
+        return None;
+    }
+    Some(emoji)
+}
+
+/// Whether `text` is only made of emoji: pictographs, ZWJ sequences, variation selectors, skin tones,
+/// flags and keycaps, but no plain text
+fn is_emoji(text: &str) -> bool {
+    let is_pictograph = |c: char| {
+        matches!(
+            c as u32,
+            0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139 | 0x2194..=0x21AA
+                | 0x231A..=0x23FF | 0x24C2 | 0x25AA..=0x25FE | 0x2600..=0x27BF | 0x2934 | 0x2935
+                | 0x2B05..=0x2B55 | 0x3030 | 0x303D | 0x3297 | 0x3299 | 0x1F000..=0x1FAFF
+        )
+    };
+    let is_modifier =
+        |c: char| matches!(c as u32, 0x200D | 0x20E3 | 0xFE0E | 0xFE0F | 0xE0020..=0xE007F);
+    // Keycaps like 1️⃣ start with an ASCII digit, # or *
+    let is_keycap = text.ends_with('\u{20E3}');
+    let mut has_pictograph = false;
+    for c in text.chars() {
+        if is_pictograph(c) || (is_keycap && matches!(c, '0'..='9' | '#' | '*')) {
+            has_pictograph = true;
+        } else if !is_modifier(c) {
+            return false;
+        }
+    }
+    has_pictograph
+}
+
+/// Whether the agent already has a reaction with `tag` on `base` which they have not removed.
+///
+/// Reactions to a message come after it, so the walk back from the newest action stops at `since`.
+fn has_active_reaction(
+    activity: &[RegisterAgentActivity],
+    base_address: &AnyLinkableHash,
+    tag: &LinkTag,
+    since: Timestamp,
+) -> ExternResult<bool> {
+    let mut newest_first: Vec<&RegisterAgentActivity> = activity.iter().collect();
+    newest_first.sort_by_key(|a| std::cmp::Reverse(a.action.action().action_seq()));
+    let mut reactions: Vec<ActionHash> = Vec::new();
+    let mut removed: Vec<ActionHash> = Vec::new();
+    for a in newest_first
+        .into_iter()
+        .take_while(|a| a.action.action().timestamp() >= since)
+    {
+        match a.action.action() {
+            Action::CreateLink(create_link)
+                if create_link.base_address == *base_address && create_link.tag == *tag =>
+            {
+                if matches!(
+                    LinkTypes::from_type(create_link.zome_index, create_link.link_type)?,
+                    Some(LinkTypes::MessageToReactions)
+                ) {
+                    reactions.push(a.action.action_address().clone());
+                }
+            }
+            Action::DeleteLink(delete_link) => {
+                removed.push(delete_link.link_add_address.clone());
+            }
+            _ => {}
+        }
+    }
+    Ok(reactions.iter().any(|hash| !removed.contains(hash)))
+}
+
+/// Links cannot cite the conversation status, so validation only enforces the end date for reactions
+fn validate_reaction_open(timestamp: Timestamp) -> ExternResult<ValidateCallbackResult> {
+    if has_conversation_ended(timestamp)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "The conversation has ended and is read-only".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_message_to_reactions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if reaction_from_tag(&tag).is_none() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "MessageToReactions tags must be an emoji of at most {} bytes",
+                    MAX_REACTION_BYTES
+                ),
+            ),
+        );
+    }
+    if target_address != AnyLinkableHash::from(action.author.clone()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageToReactions links must target their author".to_string(),
+            ),
+        );
+    }
+    let action_hash = base_address
+        .clone()
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _message: crate::Message = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MessageToReactions links must be linked from the original Message".to_string(),
+            ),
+        );
+    }
+    // The role and the earlier reactions both need the author's activity, so it is fetched once for both
+    let activity = must_get_agent_activity(
+        action.author.clone(),
+        ChainFilter::new(action.prev_action.clone()),
+    )?;
+    let role = agent_role_from_activity(&action.author, &activity)?;
+    if !role.can_write() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only members can react to messages".to_string(),
+            ),
+        );
+    }
+    if has_active_reaction(&activity, &base_address, &tag, record.action().timestamp())? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Agents can only react to a message once with each emoji".to_string(),
+            ),
+        );
+    }
+    validate_reaction_open(action.timestamp)
+}
+
+pub fn validate_delete_link_message_to_reactions(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a reaction can remove it".to_string(),
+            ),
+        );
+    }
+    validate_reaction_open(action.timestamp)
+}
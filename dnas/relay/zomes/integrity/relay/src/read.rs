@@ -0,0 +1,130 @@
+use hdi::prelude::*;
+
+pub const READ_RECEIPTS_PATH: &str = "read_receipts";
+
+/// The last message an agent read in a conversation, kept private in their own source chain
+/// so their read state survives reinstalling the app
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ReadMarker {
+    // Original action hash of the message
+    pub last_read: ActionHash,
+    // Timestamp of the message's action, so messages can be compared without fetching it
+    pub last_read_at: Timestamp,
+}
+
+/// Shared with the other members by agents who opt in to read receipts,
+/// stored in the tag of a ReadReceipts link and signed when sent as a remote signal
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+pub struct ReadReceipt {
+    pub conversation_id: String,
+    pub last_read: ActionHash,
+    pub last_read_at: Timestamp,
+}
+
+impl ReadReceipt {
+    pub fn to_tag(&self) -> ExternResult<LinkTag> {
+        let bytes = SerializedBytes::try_from(self.clone()).map_err(|e| wasm_error!(e))?;
+        Ok(LinkTag::new(bytes.bytes().clone()))
+    }
+
+    pub fn from_tag(tag: &LinkTag) -> ExternResult<ReadReceipt> {
+        ReadReceipt::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0.clone())))
+            .map_err(|e| wasm_error!(e))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+pub struct ReadReceiptEnvelope {
+    pub signature: Signature,
+    pub receipt: ReadReceipt,
+}
+
+fn validate_last_read(last_read: &ActionHash, last_read_at: Timestamp) -> ExternResult<ValidateCallbackResult> {
+    let action = must_get_action(last_read.clone())?;
+    if action.action().timestamp() != last_read_at {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "last_read_at must be the timestamp of the last read message".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_read_marker(
+    _action: EntryCreationAction,
+    read_marker: ReadMarker,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_last_read(&read_marker.last_read, read_marker.last_read_at)
+}
+
+pub fn validate_update_read_marker(
+    _action: Update,
+    read_marker: ReadMarker,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_last_read(&read_marker.last_read, read_marker.last_read_at)
+}
+
+pub fn validate_delete_read_marker(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_read_marker: ReadMarker,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_read_receipts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address != AnyLinkableHash::from(action.author.clone()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ReadReceipts links must target their author".to_string(),
+            ),
+        );
+    }
+    let path_entry_hash = Path::from(READ_RECEIPTS_PATH).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(path_entry_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ReadReceipts links must be linked from the read receipts path".to_string(),
+            ),
+        );
+    }
+    let Ok(receipt) = ReadReceipt::from_tag(&tag) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ReadReceipts tags must be a ReadReceipt".to_string(),
+            ),
+        );
+    };
+    if receipt.conversation_id != dna_info()?.modifiers.network_seed {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "ReadReceipt is not for this conversation".to_string(),
+            ),
+        );
+    }
+    validate_last_read(&receipt.last_read, receipt.last_read_at)
+}
+
+pub fn validate_delete_link_read_receipts(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a ReadReceipts link can delete it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,127 @@
+use hdi::prelude::*;
+
+use crate::{get_agent_role, MembraneProofData};
+
+/// Revokes the membrane proof with the given signature, for agents who have not been admitted yet.
+///
+/// Agents of private conversations are guests until the signer of their proof records their Admission,
+/// which is invalid when the signer revoked the proof earlier in their own chain.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct MembraneProofRevocation {
+    pub for_agent: AgentPubKey,
+    pub signature: Signature,
+    /// The revoked proof, required when the author is not the owner but signed the proof as a delegated inviter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<MembraneProofData>,
+}
+
+pub fn validate_create_membrane_proof_revocation(
+    action: EntryCreationAction,
+    revocation: MembraneProofRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    let role = get_agent_role(action.author().clone(), action.prev_action().clone())?;
+    if role.can_invite() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(data) = revocation.data else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the owner or the signer of a membrane proof can revoke it".to_string(),
+            ),
+        );
+    };
+    if data.for_agent != revocation.for_agent {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Revoked membrane proof is for another agent".to_string(),
+            ),
+        );
+    }
+    if !verify_signature(action.author().clone(), revocation.signature, data)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the owner or the signer of a membrane proof can revoke it".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_membrane_proof_revocation(
+    _action: Update,
+    _revocation: MembraneProofRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("MembraneProofRevocations cannot be updated"),
+        ),
+    )
+}
+
+pub fn validate_delete_membrane_proof_revocation(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_revocation: MembraneProofRevocation,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("MembraneProofRevocations cannot be deleted"),
+        ),
+    )
+}
+
+pub fn validate_create_link_agent_to_membrane_proof_revocations(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let revocation: crate::MembraneProofRevocation = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if *record.action().author() != action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Only the author of a MembraneProofRevocation can link to it".to_string(),
+            ),
+        );
+    }
+    if base_address != AnyLinkableHash::from(revocation.for_agent) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "MembraneProofRevocations must be linked from the revoked agent".to_string(),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_membrane_proof_revocations(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("AgentToMembraneProofRevocations links cannot be deleted"),
+        ),
+    )
+}
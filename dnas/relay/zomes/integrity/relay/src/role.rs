@@ -0,0 +1,92 @@
+use hdi::prelude::*;
+
+use crate::{dna_properties, has_membership, membrane_proof_from_activity, ownership_from_activity, Privacy};
+
+// Values of `MembraneProofData.as_role`
+pub const ROLE_MEMBER: u32 = 0;
+pub const ROLE_ADMIN: u32 = 1;
+pub const ROLE_GUEST: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Guest,
+    Member,
+    Admin,
+    Owner,
+}
+
+impl Role {
+    /// The owner role is never granted by a membrane proof, it belongs to the progenitor.
+    pub fn from_membrane_proof_role(as_role: u32) -> Option<Role> {
+        match as_role {
+            ROLE_MEMBER => Some(Role::Member),
+            ROLE_ADMIN => Some(Role::Admin),
+            ROLE_GUEST => Some(Role::Guest),
+            _ => None,
+        }
+    }
+
+    /// Post messages and manage your own contacts
+    pub fn can_write(&self) -> bool {
+        *self >= Role::Member
+    }
+
+    /// Delete messages authored by other agents
+    pub fn can_moderate(&self) -> bool {
+        *self >= Role::Admin
+    }
+
+    /// Change the conversation Config
+    pub fn can_configure(&self) -> bool {
+        *self >= Role::Admin
+    }
+
+    /// Sign membrane proofs for new members and hand over ownership
+    pub fn can_invite(&self) -> bool {
+        *self == Role::Owner
+    }
+}
+
+/// Get the role of `agent`, walking their source chain back from `chain_top`.
+///
+/// The owner is the progenitor until they propose a handover, then the successor once they accept it,
+/// or the proposer again once they reclaim it after it expired. Former owners remain admins. Everyone else has the role of the membrane proof they joined with,
+/// which in private conversations only applies once they recorded their Membership, until then they are a guest.
+pub fn get_agent_role(agent: AgentPubKey, chain_top: ActionHash) -> ExternResult<Role> {
+    if dna_properties()?.is_none() {
+        return Ok(Role::Member);
+    }
+    let activity = must_get_agent_activity(agent.clone(), ChainFilter::new(chain_top))?;
+    agent_role_from_activity(&agent, &activity)
+}
+
+/// Get the role of `agent` from their source chain activity, for validation which needs the activity too
+pub fn agent_role_from_activity(
+    agent: &AgentPubKey,
+    activity: &[RegisterAgentActivity],
+) -> ExternResult<Role> {
+    let Some(props) = dna_properties()? else {
+        return Ok(Role::Member);
+    };
+    match ownership_from_activity(activity)? {
+        Some(true) => return Ok(Role::Owner),
+        Some(false) => return Ok(Role::Admin),
+        None => {}
+    }
+    if *agent == props.progenitor {
+        return Ok(Role::Owner);
+    }
+    if props.privacy == Privacy::Public {
+        return Ok(Role::Member);
+    }
+
+    if !has_membership(activity)? {
+        return Ok(Role::Guest);
+    }
+    match membrane_proof_from_activity(activity)? {
+        Some(membrane_proof) => {
+            Ok(Role::from_membrane_proof_role(membrane_proof.as_role()).unwrap_or(Role::Guest))
+        }
+        None => Ok(Role::Guest),
+    }
+}
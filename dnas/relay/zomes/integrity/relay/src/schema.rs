@@ -0,0 +1,41 @@
+use hdi::prelude::*;
+
+// Bumped whenever an entry or link type is added, or the schema of an entry changes
+pub const INTEGRITY_SCHEMA_VERSION: u32 = 5;
+
+/// Entries whose payload carries a schema version, 0 for payloads written before versions were introduced.
+///
+/// Fields added to a versioned entry must use `#[serde(default)]` and come with a bumped `SCHEMA_VERSION`,
+/// so payloads written by older versions still decode, and `upgrade` fills in what they could not carry.
+/// Payloads claiming a newer version than this DNA knows are rejected by `validate_schema_version`.
+pub trait Versioned: Sized {
+    const SCHEMA_VERSION: u32;
+
+    fn schema_version(&self) -> u32;
+
+    fn set_schema_version(&mut self, schema_version: u32);
+
+    /// Bring a payload decoded from an older schema version up to `SCHEMA_VERSION`.
+    ///
+    /// By default only the version is bumped, for entries whose added fields decode to the right defaults.
+    fn upgrade(mut self) -> Self {
+        self.set_schema_version(Self::SCHEMA_VERSION);
+        self
+    }
+}
+
+/// Reject payloads claiming a schema version this DNA does not know about
+pub fn validate_schema_version<T: Versioned>(entry: &T) -> ExternResult<ValidateCallbackResult> {
+    if entry.schema_version() > T::SCHEMA_VERSION {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Schema version {} is newer than the latest known version {}",
+                    entry.schema_version(),
+                    T::SCHEMA_VERSION
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -1,6 +1,8 @@
-use crate::config::{APP_ID, HAPP_BUNDLE_BYTES};
-use holochain_types::prelude::AppBundle;
+use crate::config::{APP_ID, HAPP_BUNDLE_BYTES, RELAY_ROLE_NAME, RELAY_ZOME_NAME};
+use holochain_client::ZomeCallTarget;
+use holochain_types::prelude::{AppBundle, CellInfo, ExternIO};
 use lair_keystore::dependencies::sodoken::{BufRead, BufWrite};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Builder, EventLoopMessage, Listener, Manager, Runtime};
@@ -27,6 +29,7 @@ where
     >>::WindowBuilder: std::marker::Send,
 {
     builder
+        .invoke_handler(tauri::generate_handler![get_api_info, holochain_runtime])
         .plugin(tauri_plugin_holochain::async_init(
             vec_to_locked(vec![]).expect("Can't build passphrase"),
             HolochainPluginConfig::new(holochain_dir(), wan_network_config())
@@ -47,6 +50,15 @@ where
 
                         setup(handle.clone()).await.expect("Failed to setup");
 
+                        match relay_api_info(&handle).await {
+                            Ok(api_info) => {
+                                for (dna_hash, info) in api_info {
+                                    log::info!("Relay API of cell {}: {}", dna_hash, info);
+                                }
+                            }
+                            Err(err) => log::warn!("Could not get the relay API info: {:?}", err),
+                        }
+
                         let mut window = handle
                             .holochain()
                             .expect("Failed to get holochain")
@@ -131,6 +143,52 @@ async fn setup<R: Runtime>(handle: AppHandle<R>) -> anyhow::Result<()> {
     }
     Ok(())
 }
+/// Result of `get_api_info` for every relay cell, keyed by DNA hash, so clients can detect features per conversation
+async fn relay_api_info<R: Runtime>(
+    handle: &AppHandle<R>,
+) -> anyhow::Result<BTreeMap<String, serde_json::Value>> {
+    let app_ws = handle.holochain()?.app_websocket(String::from(APP_ID)).await?;
+    let app_info = app_ws
+        .app_info()
+        .await
+        .map_err(|err| tauri_plugin_holochain::Error::ConductorApiError(err))?
+        .ok_or(anyhow::anyhow!("{} is not installed", APP_ID))?;
+
+    let mut api_info = BTreeMap::new();
+    for cell_info in app_info.cell_info.get(RELAY_ROLE_NAME).into_iter().flatten() {
+        let cell_id = match cell_info {
+            CellInfo::Provisioned(cell) => cell.cell_id.clone(),
+            CellInfo::Cloned(cell) => cell.cell_id.clone(),
+            CellInfo::Stem(_) => continue,
+        };
+        let response = app_ws
+            .call_zome(
+                ZomeCallTarget::CellId(cell_id.clone()),
+                RELAY_ZOME_NAME.into(),
+                "get_api_info".into(),
+                ExternIO::encode(())?,
+            )
+            .await
+            .map_err(|err| tauri_plugin_holochain::Error::ConductorApiError(err))?;
+        let info: serde_json::Value = response.decode()?;
+        api_info.insert(cell_id.dna_hash().to_string(), info);
+    }
+    Ok(api_info)
+}
+
+/// Tells the webview the conductor is bundled, so the relay API is logged by the app itself
+#[tauri::command]
+fn holochain_runtime() -> &'static str {
+    "bundled"
+}
+
+#[tauri::command]
+async fn get_api_info<R: Runtime>(
+    handle: AppHandle<R>,
+) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    relay_api_info(&handle).await.map_err(|err| err.to_string())
+}
+
 fn wan_network_config() -> Option<WANNetworkConfig> {
     // Resolved at compile time to be able to point to local services
     if tauri::is_dev() {
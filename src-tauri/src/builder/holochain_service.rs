@@ -1,10 +1,21 @@
 use crate::config::{APP_ID, HAPP_BUNDLE_BYTES};
-use tauri::{Builder, Manager, Runtime};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{Builder, Manager, Runtime, State};
 use tauri_plugin_holochain_service_consumer::HolochainServiceConsumerExt;
 use uuid::Uuid;
 
+/// Result of `get_api_info` for every relay cell, keyed by DNA hash.
+///
+/// The conductor runs in the Android Service Runtime app and only the webview can call zomes,
+/// so the webview reports it with `report_api_info`.
+#[derive(Default)]
+struct RelayApiInfo(Mutex<BTreeMap<String, serde_json::Value>>);
+
 pub fn setup_builder<R: Runtime>(builder: Builder<R>) -> Builder<R> {
     builder
+        .manage(RelayApiInfo::default())
+        .invoke_handler(tauri::generate_handler![get_api_info, holochain_runtime, report_api_info])
         .plugin(tauri_plugin_holochain_service_consumer::init())
         .setup(|app| {
             let handle = app.handle().clone();
@@ -30,3 +41,28 @@ pub fn setup_builder<R: Runtime>(builder: Builder<R>) -> Builder<R> {
             Ok(())
         })
 }
+
+/// Tells the webview the conductor runs in the Android Service Runtime, so it must report the relay API
+#[tauri::command]
+fn holochain_runtime() -> &'static str {
+    "service"
+}
+
+#[tauri::command]
+fn report_api_info(state: State<RelayApiInfo>, dna_hash: String, info: serde_json::Value) {
+    log::info!("Relay API of cell {}: {}", dna_hash, info);
+    state
+        .0
+        .lock()
+        .expect("Relay API info lock poisoned")
+        .insert(dna_hash, info);
+}
+
+#[tauri::command]
+fn get_api_info(state: State<RelayApiInfo>) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    state
+        .0
+        .lock()
+        .map(|api_info| api_info.clone())
+        .map_err(|err| err.to_string())
+}
@@ -1,3 +1,7 @@
 pub const APP_ID: &'static str = "volla-messages";
 
 pub const HAPP_BUNDLE_BYTES: &'static [u8] = include_bytes!("../../workdir/relay.happ");
+
+pub const RELAY_ROLE_NAME: &'static str = "relay";
+
+pub const RELAY_ZOME_NAME: &'static str = "relay";
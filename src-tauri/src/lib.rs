@@ -15,6 +15,7 @@ pub fn run() {
         .plugin(
             tauri_plugin_log::Builder::default()
                 .level(log::LevelFilter::Warn)
+                .level_for("tauri_app_lib", log::LevelFilter::Info)
                 .build(),
         );
     #[cfg(mobile)]
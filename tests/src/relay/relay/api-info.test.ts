@@ -0,0 +1,29 @@
+import { assert, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+
+test('get_api_info describes the coordinator', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Works in the provisioned cell too, which has no conversation properties
+    const apiInfo: any = await alice.cells[0].callZome({
+      zome_name: "relay",
+      fn_name: "get_api_info",
+      payload: null,
+    });
+    assert.match(apiInfo.coordinator_version, /^\d+\.\d+\.\d+/);
+    assert.isAbove(apiInfo.integrity_schema_version, 0);
+    assert.equal(apiInfo.remote_signal_protocol_version, 1);
+    for (const feature of ["roles", "invite_tokens", "ownership_handover", "replies", "reactions", "remote_signal_envelope"]) {
+      assert.include(apiInfo.features, feature);
+    }
+  });
+});
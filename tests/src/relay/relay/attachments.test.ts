@@ -0,0 +1,65 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, CallableCell } from '@holochain/tryorama';
+import { EntryHash } from '@holochain/client';
+
+import { createConversation, Privacy, sampleConversationMessage, sendMessage } from './common.js';
+
+// Store a small file with the file_storage zome, the way the UI uploads attachments
+async function uploadFile(cell: CallableCell, name: string, fileType: string): Promise<[EntryHash, EntryHash]> {
+    const chunkHash: EntryHash = await cell.callZome({
+      zome_name: "file_storage",
+      fn_name: "create_file_chunk",
+      payload: new Uint8Array([1, 2, 3, 4]),
+    });
+    const fileMetadataHash: EntryHash = await cell.callZome({
+      zome_name: "file_storage",
+      fn_name: "create_file_metadata",
+      payload: {
+        name,
+        last_modified: Date.now() * 1000,
+        size: 4,
+        file_type: fileType,
+        chunks_hashes: [chunkHash],
+      },
+    });
+    return [fileMetadataHash, chunkHash];
+}
+
+test('attachments must match the file metadata they reference', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation and uploads a file
+    const [, aliceCell] = await createConversation(alice, Privacy.Public);
+    const [fileMetadataHash, chunkHash] = await uploadFile(aliceCell, "photo.png", "image/png");
+    const file = {
+      name: "photo.png",
+      last_modified: Date.now() * 1000,
+      size: 4,
+      file_type: "image/png",
+      storage_entry_hash: fileMetadataHash,
+    };
+
+    assert.ok(await sendMessage(aliceCell, sampleConversationMessage({ images: [file] })));
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [{ ...file, size: 8 }] })),
+    ).rejects.toThrow(/Attachment photo.png has size 8 but its stored file has size 4/);
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [{ ...file, file_type: "image/jpeg" }] })),
+    ).rejects.toThrow(/Attachment photo.png has type image\/jpeg but its stored file has type image\/png/);
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [{ ...file, storage_entry_hash: chunkHash }] })),
+    ).rejects.toThrow(/Attachment photo.png does not reference stored file metadata/);
+  });
+});
@@ -0,0 +1,55 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { ActionHash, NewEntryAction, Record } from '@holochain/client';
+
+import { createConversation, entryOf, Privacy, sendMessage } from './common.js';
+
+const DAY_MS = 1000 * 60 * 60 * 24;
+
+test('Messages are bucketed by their action timestamp', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation ten days ago, with one bucket per day
+    const [, aliceCell] = await createConversation(alice, Privacy.Public, {
+      created: Date.now() - 10 * DAY_MS,
+      bucket_range_ms: DAY_MS,
+    });
+
+    // The bucket sent by the client is replaced with the one matching the action timestamp
+    const record: Record = await sendMessage(aliceCell);
+    const bucket: number = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_bucket_for_timestamp",
+      payload: (record.signed_action.hashed.content as NewEntryAction).timestamp,
+    });
+    assert.equal(bucket, 10);
+    assert.equal(entryOf(record).bucket, bucket);
+
+    const hashes: ActionHash[] = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_message_hashes",
+      payload: { bucket, count: 10 },
+    });
+    assert.deepEqual(hashes, [record.signed_action.hashed.hash]);
+
+    // An update cannot move the Message to another bucket
+    await expect(aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: record.signed_action.hashed.hash,
+        previous_message_hash: record.signed_action.hashed.hash,
+        updated_message: { ...entryOf(record), bucket: 3 },
+      },
+    })).rejects.toThrow(/Message bucket cannot be changed by an update/);
+  });
+});
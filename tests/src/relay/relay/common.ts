@@ -1,5 +1,7 @@
-import { CallableCell } from '@holochain/tryorama';
-import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeActionHash, fakeAgentPubKey, fakeEntryHash, fakeDnaHash } from '@holochain/client';
+import { CallableCell, Player, getCallableCell, pause } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, AgentPubKey, MembraneProof, Record, Signal, SignalType, AppBundleSource, encodeHashToBase64, fakeActionHash, fakeAgentPubKey, fakeEntryHash, fakeDnaHash } from '@holochain/client';
+import { decode, encode } from '@msgpack/msgpack';
+import { generateKeyPairSync, KeyObject, sign } from 'node:crypto';
 
 
 
@@ -39,3 +41,162 @@ export async function createMessage(cell: CallableCell, message = undefined): Pr
     });
 }
 
+
+
+export enum Privacy {
+  Private,
+  Public,
+}
+
+export enum MembraneProofRole {
+  Member = 0,
+  Admin = 1,
+  Guest = 2,
+}
+
+export interface Conversation {
+  networkSeed: string;
+  properties: any;
+}
+
+export function sampleConversation(progenitor: AgentPubKey, privacy: Privacy, partialProperties = {}): Conversation {
+    return {
+      networkSeed: crypto.randomUUID(),
+      properties: {
+        ...{
+          created: new Date().getTime(),
+          privacy,
+          progenitor: encodeHashToBase64(progenitor),
+        },
+        ...partialProperties
+      },
+    };
+}
+
+// Clone the relay cell for a conversation, the way the UI creates and joins conversations
+export async function joinConversation(player: Player, conversation: Conversation, membraneProof: MembraneProof | undefined = undefined): Promise<CallableCell> {
+    const clonedCell = await player.appWs.createCloneCell({
+      role_name: "relay",
+      modifiers: {
+        network_seed: conversation.networkSeed,
+        properties: conversation.properties,
+      },
+      membrane_proof: membraneProof,
+    });
+    return getCallableCell(player.appWs, clonedCell);
+}
+
+export async function createConversation(player: Player, privacy: Privacy, partialProperties = {}): Promise<[Conversation, CallableCell]> {
+    const conversation = sampleConversation(player.agentPubKey, privacy, partialProperties);
+    return [conversation, await joinConversation(player, conversation)];
+}
+
+export async function generateMembraneProof(cell: CallableCell, conversation: Conversation, forAgent: AgentPubKey, partialData = {}): Promise<MembraneProof> {
+    return cell.callZome({
+      zome_name: "relay",
+      fn_name: "generate_membrane_proof",
+      payload: {
+        ...{
+          conversation_id: conversation.networkSeed,
+          for_agent: forAgent,
+          as_role: MembraneProofRole.Member,
+        },
+        ...partialData
+      },
+    });
+}
+
+// Ask the signer of the cell's membrane proof to admit it, retrying until the signer can be reached
+export async function requestAdmission(cell: CallableCell, timeoutMs = 30000): Promise<void> {
+    const start = Date.now();
+    while (Date.now() - start < timeoutMs) {
+      const admitted: boolean = await cell.callZome({ zome_name: "relay", fn_name: "request_admission", payload: null });
+      if (admitted) return;
+      await pause(500);
+    }
+    throw new Error("Timed out waiting for admission");
+}
+
+// `inviter` signs a membrane proof for `player`, who joins the conversation with it and gets admitted
+export async function inviteToConversation(inviter: CallableCell, conversation: Conversation, player: Player, partialData = {}): Promise<CallableCell> {
+    const membraneProof = await generateMembraneProof(inviter, conversation, player.agentPubKey, partialData);
+    const cell = await joinConversation(player, conversation, membraneProof);
+    await requestAdmission(cell);
+    return cell;
+}
+
+// Generate an invite token with a fresh ed25519 key pair, returning the token and its secret key
+export async function generateInviteToken(cell: CallableCell, partialInput = {}): Promise<[MembraneProof, KeyObject]> {
+    const { publicKey, privateKey } = generateKeyPairSync("ed25519");
+    // Sent as an array of bytes, since the key is a Vec<u8> rather than a hash or signature
+    const key = Array.from(Buffer.from(publicKey.export({ format: "jwk" }).x!, "base64url"));
+    const token: MembraneProof = await cell.callZome({
+      zome_name: "relay",
+      fn_name: "generate_invite_token",
+      payload: {
+        ...{
+          key,
+          max_uses: 1,
+          expires_at: Date.now() * 1000 + 1000 * 1000 * 60 * 60,
+          as_role: MembraneProofRole.Member,
+        },
+        ...partialInput
+      },
+    });
+    return [token, privateKey];
+}
+
+// Sign `agent`'s public key with the secret key of an invite token, giving the membrane proof they join with
+export function signInviteToken(token: MembraneProof, secretKey: KeyObject, agent: AgentPubKey): MembraneProof {
+    const envelope: any = decode(token);
+    return encode({ ...envelope, agent_signature: sign(null, agent, secretKey) });
+}
+
+export function sampleConversationMessage(partialMessage = {}) {
+    return {
+        ...{
+	  content: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+	  bucket: 0,
+	  images: [],
+        },
+        ...partialMessage
+    };
+}
+
+export async function sendMessage(cell: CallableCell, message = undefined, agents: AgentPubKey[] = []): Promise<Record> {
+    return cell.callZome({
+      zome_name: "relay",
+      fn_name: "create_message",
+      payload: {
+        message: message || sampleConversationMessage(),
+        agents,
+      },
+    });
+}
+
+export function entryOf(record: Record): any {
+    return decode((record.entry as any).Present.entry);
+}
+
+// Collect the relay signals emitted to `player` by `cell`
+export function collectSignals(player: Player, cell: CallableCell): any[] {
+    const signals: any[] = [];
+    player.appWs.on("signal", (signal: Signal) => {
+      if (!(SignalType.App in signal)) return;
+      const appSignal = signal[SignalType.App];
+      if (appSignal.zome_name !== "relay") return;
+      if (encodeHashToBase64(appSignal.cell_id[0]) !== encodeHashToBase64(cell.cell_id[0])) return;
+      signals.push(appSignal.payload);
+    });
+    return signals;
+}
+
+export async function waitForSignal(signals: any[], predicate: (signal: any) => boolean, timeoutMs = 10000): Promise<any> {
+    const start = Date.now();
+    while (Date.now() - start < timeoutMs) {
+      const signal = signals.find(predicate);
+      if (signal) return signal;
+      await pause(100);
+    }
+    return undefined;
+}
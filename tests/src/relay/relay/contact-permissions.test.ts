@@ -0,0 +1,81 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { createConversation, entryOf, joinConversation, Privacy } from './common.js';
+
+test('only the author can update or delete a Contact', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice adds Bob as a Contact
+    const record: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "create_contact",
+      payload: {
+        public_key: bob.agentPubKey,
+        first_name: "Bob",
+        last_name: "Builder",
+        avatar: "",
+      },
+    });
+    const originalActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // Bob cannot update Alice's Contact
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_contact",
+      payload: {
+        original_contact_hash: originalActionHash,
+        previous_contact_hash: originalActionHash,
+        updated_contact: { ...entryOf(record), first_name: "Robert" },
+      },
+    })).rejects.toThrow(/Only the author of a Contact can update it/);
+
+    // Nor delete it, or the links to it
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_contact",
+      payload: originalActionHash,
+    })).rejects.toThrow(/Only the author of a (Contact|ContactToContacts link) can delete it/);
+
+    // Alice can do both
+    const updatedRecord: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_contact",
+      payload: {
+        original_contact_hash: originalActionHash,
+        previous_contact_hash: originalActionHash,
+        updated_contact: { ...entryOf(record), first_name: "Robert" },
+      },
+    });
+    assert.ok(updatedRecord);
+
+    const deleteActionHash = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_contact",
+      payload: originalActionHash,
+    });
+    assert.ok(deleteActionHash);
+  });
+});
@@ -0,0 +1,84 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+
+import { createConversation, generateMembraneProof, inviteToConversation, MembraneProofRole, Privacy } from './common.js';
+
+test('delegates can invite members until their delegation is revoked', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 4 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol, dave] = await scenario.addPlayersWithApps([appSource, appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation, invites Bob and delegates her invite authority to him
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "grant_invite_authority",
+      payload: bob.agentPubKey,
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // Bob invites Carol as a member
+    const carolCell = await inviteToConversation(bobCell, conversation, carol);
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Member");
+
+    // Delegates cannot invite admins
+    await expect(
+      generateMembraneProof(bobCell, conversation, dave.agentPubKey, { as_role: MembraneProofRole.Admin }),
+    ).rejects.toThrow(/unauthorized/);
+
+    // Carol was not granted invite authority, so she can neither invite nor delegate
+    await expect(
+      generateMembraneProof(carolCell, conversation, dave.agentPubKey),
+    ).rejects.toThrow(/unauthorized/);
+    await expect(carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "grant_invite_authority",
+      payload: dave.agentPubKey,
+    })).rejects.toThrow(/unauthorized/);
+
+    // Alice revokes Bob's delegation
+    const delegation: any = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_invite_delegation_for_agent",
+      payload: bob.agentPubKey,
+    });
+    assert.equal(delegation.chain.length, 1);
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "revoke_invite_delegation",
+      payload: delegation.chain[0],
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    assert.equal(await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_invite_delegation_for_agent",
+      payload: bob.agentPubKey,
+    }), null);
+    await expect(
+      generateMembraneProof(bobCell, conversation, dave.agentPubKey),
+    ).rejects.toThrow(/unauthorized/);
+
+    // Carol joined before the revocation, so she remains a member
+    const isRevoked: boolean = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "is_agent_revoked",
+      payload: carol.agentPubKey,
+    });
+    assert.isFalse(isRevoked);
+  });
+});
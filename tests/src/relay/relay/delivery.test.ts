@@ -0,0 +1,105 @@
+import { assert, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { encodeHashToBase64, Record } from '@holochain/client';
+
+import { collectSignals, createConversation, joinConversation, Privacy, sendMessage, waitForSignal } from './common.js';
+
+test('recipients acknowledge the messages they receive', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    const aliceSignals = collectSignals(alice, aliceCell);
+    const bobSignals = collectSignals(bob, bobCell);
+
+    // Alice sends a message to Bob
+    const record: Record = await sendMessage(aliceCell, undefined, [bob.agentPubKey]);
+    const messageHash = record.signed_action.hashed.hash;
+
+    const received = await waitForSignal(bobSignals, signal => signal.type === "Message");
+    assert.ok(received);
+    assert.deepEqual(received.from, alice.agentPubKey);
+
+    // Bob acknowledges it
+    const delivered = await waitForSignal(aliceSignals, signal => signal.type === "Delivered");
+    assert.ok(delivered);
+    assert.deepEqual(delivered.message, messageHash);
+    assert.deepEqual(delivered.from, bob.agentPubKey);
+
+    const status: any[] = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_delivery_status",
+      payload: messageHash,
+    });
+    assert.equal(status.length, 1);
+    assert.deepEqual(status[0].agent, bob.agentPubKey);
+    assert.equal(status[0].status, "Delivered");
+    assert.equal(status[0].attempts, 1);
+
+    // Messages sent to nobody have no outbox entry
+    const unsent: Record = await sendMessage(aliceCell);
+    assert.deepEqual(await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_delivery_status",
+      payload: unsent.signed_action.hashed.hash,
+    }), []);
+  });
+});
+
+test('delivery status is tracked for each recipient', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins but Carol doesn't
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    await joinConversation(bob, conversation);
+    const aliceSignals = collectSignals(alice, aliceCell);
+
+    // Alice sends a message to both, and only Bob acknowledges it
+    const record: Record = await sendMessage(aliceCell, undefined, [bob.agentPubKey, carol.agentPubKey]);
+    const messageHash = record.signed_action.hashed.hash;
+    assert.ok(await waitForSignal(aliceSignals, signal => signal.type === "Delivered"));
+
+    const status: any[] = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_delivery_status",
+      payload: messageHash,
+    });
+    assert.equal(status.length, 2);
+    const bobStatus = status.find(s => encodeHashToBase64(s.agent) === encodeHashToBase64(bob.agentPubKey));
+    const carolStatus = status.find(s => encodeHashToBase64(s.agent) === encodeHashToBase64(carol.agentPubKey));
+    assert.equal(bobStatus.status, "Delivered");
+    assert.equal(bobStatus.attempts, 1);
+    assert.equal(carolStatus.status, "Sent");
+    assert.equal(carolStatus.attempts, 1);
+  });
+});
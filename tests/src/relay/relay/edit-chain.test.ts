@@ -0,0 +1,95 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { ActionHash, Record } from '@holochain/client';
+
+import { createConversation, entryOf, joinConversation, Privacy, sendMessage } from './common.js';
+
+async function editMessage(cell: CallableCell, original: Record, previousHash: ActionHash, content: string): Promise<Record> {
+    return cell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: original.signed_action.hashed.hash,
+        previous_message_hash: previousHash,
+        updated_message: { ...entryOf(original), content },
+      },
+    });
+}
+
+test('MessageUpdates links must follow the edit chain of their Message', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation with two messages
+    const [, aliceCell] = await createConversation(alice, Privacy.Public);
+    const first: Record = await sendMessage(aliceCell);
+    const second: Record = await sendMessage(aliceCell);
+
+    // An edit of the second message cannot be linked as a revision of the first
+    await expect(
+      editMessage(aliceCell, first, second.signed_action.hashed.hash, "Edited"),
+    ).rejects.toThrow(/MessageUpdates links must target an update of the Message they are linked from/);
+
+    // Revisions are linked from the original Message, not from another revision
+    const edit: Record = await editMessage(aliceCell, first, first.signed_action.hashed.hash, "Edited");
+    await expect(
+      editMessage(aliceCell, edit, edit.signed_action.hashed.hash, "Edited again"),
+    ).rejects.toThrow(/MessageUpdates links must be linked from the original Message/);
+  });
+});
+
+test('the latest revision is the tip of the longest edit chain', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice edits her message twice from the original, then edits the first edit
+    const original: Record = await sendMessage(aliceCell);
+    const first: Record = await editMessage(aliceCell, original, original.signed_action.hashed.hash, "First edit");
+    await editMessage(aliceCell, original, original.signed_action.hashed.hash, "Branched edit");
+    await editMessage(aliceCell, original, first.signed_action.hashed.hash, "Edit of the first edit");
+
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const revisions: Record[] = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_all_revisions_for_message",
+      payload: original.signed_action.hashed.hash,
+    });
+    assert.deepEqual(
+      revisions.map(record => entryOf(record).content),
+      [entryOf(original).content, "First edit", "Branched edit", "Edit of the first edit"],
+    );
+
+    const latest: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_latest_message",
+      payload: original.signed_action.hashed.hash,
+    });
+    assert.equal(latest.message.content, "Edit of the first edit");
+  });
+});
@@ -0,0 +1,59 @@
+import { assert, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { encodeHashToBase64, fakeActionHash } from '@holochain/client';
+
+import { createConversation, generateMembraneProof, inviteToConversation, Privacy } from './common.js';
+
+// The RelayError serialized into the guest error of a rejected zome call
+async function relayError(call: Promise<any>): Promise<any> {
+    try {
+      await call;
+    } catch (e) {
+      // The guest error is debug formatted within the conductor error, so its quotes are escaped
+      const message = String((e as any).message ?? e).replace(/\\"/g, '"');
+      const json = message.match(/\{"code".*?\}/);
+      assert.ok(json, `no RelayError in ${e}`);
+      return JSON.parse(json![0]);
+    }
+    assert.fail("zome call did not fail");
+}
+
+test('zome functions fail with typed error codes', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+
+    const missingHash = await fakeActionHash();
+    assert.deepEqual(
+      await relayError(aliceCell.callZome({ zome_name: "relay", fn_name: "delete_message", payload: missingHash })),
+      { code: "not_found", kind: "Message", hash: encodeHashToBase64(missingHash) },
+    );
+
+    assert.deepEqual(
+      await relayError(bobCell.callZome({ zome_name: "relay", fn_name: "set_config", payload: { title: "", image: "" } })),
+      { code: "unauthorized", action: "set_config" },
+    );
+
+    assert.deepEqual(
+      await relayError(generateMembraneProof(aliceCell, conversation, bob.agentPubKey, { as_role: 7 })),
+      { code: "unknown_role", role: 7 },
+    );
+  });
+});
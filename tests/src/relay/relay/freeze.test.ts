@@ -0,0 +1,87 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { createConversation, entryOf, inviteToConversation, Privacy, sendMessage } from './common.js';
+
+test('conversations are read-only after their end date', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation which has already ended
+    const endsAtMs = Date.now() - 1000;
+    const [, aliceCell] = await createConversation(alice, Privacy.Public, { ends_at_ms: endsAtMs });
+
+    const closedAt: number = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_conversation_closed_at",
+      payload: null,
+    });
+    assert.equal(closedAt, endsAtMs * 1000);
+
+    await expect(sendMessage(aliceCell)).rejects.toThrow(/conversation_closed/);
+  });
+});
+
+test('the owner can freeze a conversation', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob, who posts a message
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    const record: Record = await sendMessage(bobCell);
+
+    // Only the owner can freeze it
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "freeze_conversation",
+      payload: { reason: "Bob wants quiet" },
+    })).rejects.toThrow(/unauthorized/);
+
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "freeze_conversation",
+      payload: { reason: "Archived" },
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // The freeze is an update of the status that every message cites, so Bob sees it too
+    const status: Record = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_conversation_status",
+      payload: null,
+    });
+    assert.equal(entryOf(status).frozen, "Archived");
+
+    // Bob can still read the conversation, but no longer post to it
+    const messages: any[] = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_messages_for_buckets",
+      payload: [entryOf(record).bucket],
+    });
+    assert.equal(messages.length, 1);
+    await expect(sendMessage(bobCell)).rejects.toThrow(/conversation_closed/);
+  });
+});
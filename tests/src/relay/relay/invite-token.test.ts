@@ -0,0 +1,95 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { generateKeyPairSync } from 'node:crypto';
+
+import { createConversation, generateInviteToken, joinConversation, Privacy, requestAdmission, signInviteToken } from './common.js';
+
+const HOUR_US = 1000 * 1000 * 60 * 60;
+
+test('invite tokens can be used by several agents up to their maximum uses', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and an invite token which can be used once
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const [token, secretKey] = await generateInviteToken(aliceCell, { max_uses: 1 });
+
+    // Bob joins with it, and Alice admits him
+    const bobCell = await joinConversation(bob, conversation, signInviteToken(token, secretKey, bob.agentPubKey));
+    await requestAdmission(bobCell);
+    assert.equal(await bobCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Member");
+
+    // Carol passes genesis with the same token, but Alice refuses to admit her as Bob used it up
+    const carolCell = await joinConversation(carol, conversation, signInviteToken(token, secretKey, carol.agentPubKey));
+    await expect(requestAdmission(carolCell)).rejects.toThrow(/Invite token has reached its maximum uses/);
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Guest");
+  });
+});
+
+test('invite tokens must be signed for the joining agent with their secret key', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const [token, secretKey] = await generateInviteToken(aliceCell, { max_uses: 10 });
+
+    // The token alone is not enough to join
+    await expect(joinConversation(bob, conversation, token)).rejects.toThrow();
+
+    // Nor is a token signed with another key
+    const { privateKey: otherKey } = generateKeyPairSync("ed25519");
+    await expect(joinConversation(bob, conversation, signInviteToken(token, otherKey, bob.agentPubKey))).rejects.toThrow();
+
+    // Bob's published membrane proof cannot be reused by Carol
+    const bobProof = signInviteToken(token, secretKey, bob.agentPubKey);
+    await expect(joinConversation(carol, conversation, bobProof)).rejects.toThrow();
+    await joinConversation(bob, conversation, bobProof);
+  });
+});
+
+test('expired invite tokens cannot be used', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Alice creates a private conversation and an invite token which has already expired
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const [token, secretKey] = await generateInviteToken(aliceCell, {
+      max_uses: 10,
+      expires_at: Date.now() * 1000 - HOUR_US,
+    });
+
+    await expect(joinConversation(bob, conversation, signInviteToken(token, secretKey, bob.agentPubKey))).rejects.toThrow();
+  });
+});
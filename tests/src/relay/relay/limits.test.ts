@@ -0,0 +1,60 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { fakeEntryHash } from '@holochain/client';
+
+import { createConversation, Privacy, sampleConversationMessage, sendMessage } from './common.js';
+
+async function sampleFile(partialFile = {}) {
+    return {
+        ...{
+	  name: "photo.png",
+	  last_modified: Date.now() * 1000,
+	  size: 1024,
+	  file_type: "image/png",
+	  storage_entry_hash: await fakeEntryHash(),
+        },
+        ...partialFile
+    };
+}
+
+test('Messages must respect the content and attachment limits of the conversation', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation with tight limits
+    const [, aliceCell] = await createConversation(alice, Privacy.Public, {
+      limits: {
+        max_content_length: 20,
+        max_attachments: 1,
+        max_attachment_size: 2048,
+        allowed_file_types: ["image/*"],
+      },
+    });
+
+    assert.ok(await sendMessage(aliceCell, sampleConversationMessage({ content: "Short enough" })));
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ content: "This message is longer than twenty bytes" })),
+    ).rejects.toThrow(/Message content must be at most 20 bytes/);
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [await sampleFile(), await sampleFile()] })),
+    ).rejects.toThrow(/Message can have at most 1 attachments/);
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [await sampleFile({ size: 4096 })] })),
+    ).rejects.toThrow(/Attachment photo.png must be at most 2048 bytes/);
+
+    await expect(
+      sendMessage(aliceCell, sampleConversationMessage({ images: [await sampleFile({ name: "doc.pdf", file_type: "application/pdf" })] })),
+    ).rejects.toThrow(/Attachment type application\/pdf is not allowed/);
+  });
+});
@@ -0,0 +1,114 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Link, Record } from '@holochain/client';
+
+import { createConversation, entryOf, inviteToConversation, joinConversation, MembraneProofRole, Privacy, sendMessage } from './common.js';
+
+test('only the author of an AllMessages link can delete it', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, carol] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Carol as an admin
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const carolCell = await inviteToConversation(aliceCell, conversation, carol, { as_role: MembraneProofRole.Admin });
+
+    const moderated: Record = await sendMessage(aliceCell);
+    const retracted: Record = await sendMessage(aliceCell);
+    const bucket = entryOf(moderated).bucket;
+    await dhtSync([alice, carol], aliceCell.cell_id[0]);
+
+    // Carol moderates one message, which leaves Alice's link to it in place
+    await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_message",
+      payload: moderated.signed_action.hashed.hash,
+    });
+    // Alice deletes the other one along with her link
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_message",
+      payload: retracted.signed_action.hashed.hash,
+    });
+    await dhtSync([alice, carol], aliceCell.cell_id[0]);
+
+    const links: Link[] = await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_message_links_for_buckets",
+      payload: [bucket],
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, moderated.signed_action.hashed.hash);
+
+    // Both messages are gone
+    const messages: any[] = await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_messages_for_buckets",
+      payload: [bucket],
+    });
+    assert.equal(messages.length, 0);
+  });
+});
+
+test('only the author of a ContactToContacts link can delete it', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice adds Bob as a Contact, which links it from Bob's key
+    const record: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "create_contact",
+      payload: {
+        public_key: bob.agentPubKey,
+        first_name: "Bob",
+        last_name: "Builder",
+        avatar: "",
+      },
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const contactLinks: Link[] = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_contacts_for_contact",
+      payload: bob.agentPubKey,
+    });
+    assert.equal(contactLinks.length, 1);
+
+    // Bob cannot delete the link from his own key to Alice's Contact
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_contact",
+      payload: record.signed_action.hashed.hash,
+    })).rejects.toThrow(/Only the author of a ContactToContacts link can delete it/);
+  });
+});
@@ -0,0 +1,101 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { createConversation, entryOf, joinConversation, Privacy, sendMessage } from './common.js';
+
+test('only the author can update a Message', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice creates a Message
+    const record: Record = await sendMessage(aliceCell);
+    const originalActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // Bob cannot update Alice's Message
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: originalActionHash,
+        previous_message_hash: originalActionHash,
+        updated_message: { ...entryOf(record), content: "Edited by Bob" },
+      },
+    })).rejects.toThrow(/Only the author of a Message can update it/);
+
+    // Alice can
+    const updatedRecord: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: originalActionHash,
+        previous_message_hash: originalActionHash,
+        updated_message: { ...entryOf(record), content: "Edited by Alice" },
+      },
+    });
+    assert.ok(updatedRecord);
+  });
+});
+
+test('only the author can delete a Message', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice creates a Message
+    const record: Record = await sendMessage(aliceCell);
+
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // Bob is neither its author nor a moderator, so he cannot delete it
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_message",
+      payload: record.signed_action.hashed.hash,
+    })).rejects.toThrow(/Only the author of a Message or a moderator can delete it/);
+
+    // Alice can
+    const deleteActionHash = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_message",
+      payload: record.signed_action.hashed.hash,
+    });
+    assert.ok(deleteActionHash);
+  });
+});
@@ -0,0 +1,140 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { ActionHash, AgentPubKey, Record } from '@holochain/client';
+
+import { createConversation, generateMembraneProof, inviteToConversation, Privacy } from './common.js';
+
+test('ownership moves to the successor when they accept the handover', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob and Carol
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    const carolCell = await inviteToConversation(aliceCell, conversation, carol);
+
+    // Only the owner can propose a handover
+    await expect(carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "propose_ownership_handover",
+      payload: carol.agentPubKey,
+    })).rejects.toThrow(/unauthorized/);
+
+    // Alice proposes Bob as the new owner, which makes her an admin straight away
+    const handover: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "propose_ownership_handover",
+      payload: bob.agentPubKey,
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+    const owner: AgentPubKey = await carolCell.callZome({ zome_name: "relay", fn_name: "get_current_owner", payload: null });
+    assert.deepEqual(owner, alice.agentPubKey);
+    assert.equal(await aliceCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Admin");
+    await expect(
+      generateMembraneProof(aliceCell, conversation, carol.agentPubKey),
+    ).rejects.toThrow(/unauthorized/);
+
+    // Carol cannot accept a handover proposed to Bob
+    await expect(carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "accept_ownership_handover",
+      payload: handover.signed_action.hashed.hash,
+    })).rejects.toThrow(/Only the successor can accept an OwnershipHandover/);
+
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "accept_ownership_handover",
+      payload: handover.signed_action.hashed.hash,
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    const newOwner: AgentPubKey = await carolCell.callZome({ zome_name: "relay", fn_name: "get_current_owner", payload: null });
+    assert.deepEqual(newOwner, bob.agentPubKey);
+    assert.equal(await bobCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Owner");
+    assert.equal(await aliceCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Admin");
+
+    // Alice can no longer invite, Bob can
+    await expect(
+      generateMembraneProof(aliceCell, conversation, carol.agentPubKey),
+    ).rejects.toThrow(/unauthorized/);
+    assert.ok(await generateMembraneProof(bobCell, conversation, carol.agentPubKey));
+  });
+});
+
+test('cancelled handovers cannot be accepted, and ownership comes back once they expire', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+
+    // Alice proposes Bob as the new owner, then changes her mind
+    const handover: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "propose_ownership_handover",
+      payload: bob.agentPubKey,
+    });
+    const handoverHash: ActionHash = handover.signed_action.hashed.hash;
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "cancel_ownership_handover",
+      payload: handoverHash,
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const handovers: Record[] = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_ownership_handovers_for_agent",
+      payload: bob.agentPubKey,
+    });
+    assert.equal(handovers.length, 0);
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "accept_ownership_handover",
+      payload: handoverHash,
+    })).rejects.toThrow(/handover_not_pending/);
+
+    // A handover cannot be cancelled twice
+    await expect(aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "cancel_ownership_handover",
+      payload: handoverHash,
+    })).rejects.toThrow(/handover_not_pending/);
+
+    // Alice stays an admin until the handover expires, so that she and Bob never both own the conversation
+    assert.equal(await aliceCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Admin");
+    await expect(aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "reclaim_ownership",
+      payload: handoverHash,
+    })).rejects.toThrow(/handover_not_expired/);
+  });
+});
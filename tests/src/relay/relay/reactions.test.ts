@@ -0,0 +1,115 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { ActionHash, Record } from '@holochain/client';
+
+import { createConversation, inviteToConversation, MembraneProofRole, Privacy, sendMessage } from './common.js';
+
+test('members can add and remove reactions while the conversation is open', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation, inviting Bob as a member and Carol as a guest
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    const carolCell = await inviteToConversation(aliceCell, conversation, carol, { as_role: MembraneProofRole.Guest });
+
+    const record: Record = await sendMessage(aliceCell);
+    const message = record.signed_action.hashed.hash;
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    // Reacting twice with the same emoji only adds one reaction
+    const reaction: ActionHash = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "add_reaction",
+      payload: { message, emoji: "👍", agents: [] },
+    });
+    const again: ActionHash = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "add_reaction",
+      payload: { message, emoji: "👍", agents: [] },
+    });
+    assert.deepEqual(again, reaction);
+
+    // Reactions must be emoji, not empty or plain text
+    for (const emoji of ["", "lol", "👍 nice"]) {
+      await expect(bobCell.callZome({
+        zome_name: "relay",
+        fn_name: "add_reaction",
+        payload: { message, emoji, agents: [] },
+      })).rejects.toThrow(/malformed_record/);
+    }
+
+    // Guests cannot react
+    await expect(carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "add_reaction",
+      payload: { message, emoji: "👍", agents: [] },
+    })).rejects.toThrow(/Only members can react to messages/);
+
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+    let reactions: any[] = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_reactions_for_messages",
+      payload: [message],
+    });
+    assert.equal(reactions[0].reactions.length, 1);
+    assert.equal(reactions[0].reactions[0].emoji, "👍");
+    assert.deepEqual(reactions[0].reactions[0].agent, bob.agentPubKey);
+
+    // Alice has no reaction of her own to remove, so Bob's stays
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "remove_reaction",
+      payload: { message, emoji: "👍", agents: [] },
+    });
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "add_reaction",
+      payload: { message, emoji: "🎉", agents: [] },
+    });
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "remove_reaction",
+      payload: { message, emoji: "🎉", agents: [] },
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+    reactions = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_reactions_for_messages",
+      payload: [message],
+    });
+    assert.deepEqual(reactions[0].reactions.map((r: any) => r.emoji), ["👍"]);
+
+    // Once the conversation is frozen, reactions can be neither added nor removed
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "freeze_conversation",
+      payload: { reason: "Archived" },
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "remove_reaction",
+      payload: { message, emoji: "👍", agents: [] },
+    })).rejects.toThrow(/conversation_closed/);
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "add_reaction",
+      payload: { message, emoji: "🎉", agents: [] },
+    })).rejects.toThrow(/conversation_closed/);
+  });
+});
@@ -0,0 +1,76 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { collectSignals, createConversation, joinConversation, Privacy, sendMessage, waitForSignal } from './common.js';
+
+test('read markers only move forward and shared receipts are visible to members', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob and Carol join
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    const carolCell = await joinConversation(carol, conversation);
+    const aliceSignals = collectSignals(alice, aliceCell);
+
+    const first: Record = await sendMessage(aliceCell);
+    const second: Record = await sendMessage(aliceCell);
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    // Bob reads up to the second message and shares a receipt with Alice
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "mark_read",
+      payload: { message: second.signed_action.hashed.hash, share: true, agents: [alice.agentPubKey] },
+    });
+    const receipt = await waitForSignal(aliceSignals, signal => signal.type === "ReadReceipt");
+    assert.ok(receipt);
+    assert.deepEqual(receipt.from, bob.agentPubKey);
+    assert.deepEqual(receipt.receipt.last_read, second.signed_action.hashed.hash);
+
+    // Marking an older message as read leaves the marker where it was
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "mark_read",
+      payload: { message: first.signed_action.hashed.hash, share: true, agents: [] },
+    });
+    const readState: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_read_state",
+      payload: null,
+    });
+    assert.deepEqual(readState.last_read, second.signed_action.hashed.hash);
+
+    // Carol reads without sharing a receipt
+    await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "mark_read",
+      payload: { message: second.signed_action.hashed.hash, share: false, agents: [] },
+    });
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    for (const message of [first, second]) {
+      const readers: any[] = await aliceCell.callZome({
+        zome_name: "relay",
+        fn_name: "get_readers_for_message",
+        payload: message.signed_action.hashed.hash,
+      });
+      assert.deepEqual(readers.map(reader => reader.agent), [bob.agentPubKey]);
+    }
+  });
+});
@@ -0,0 +1,67 @@
+import { assert, test } from "vitest";
+
+import { runScenario, pause } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { collectSignals, createConversation, entryOf, Privacy, sendMessage, waitForSignal } from './common.js';
+
+test('remote signals from older and newer agents are accepted', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 1 player with the test app to the Scenario
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a public conversation and posts a message
+    const [_conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const aliceSignals = collectSignals(alice, aliceCell);
+    const record: Record = await sendMessage(aliceCell);
+
+    // Agents from before the envelope only sent bare message records.
+    // Alice delivers one to herself, since only she can sign as herself.
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: {
+        message: entryOf(record),
+        original_action: record.signed_action.hashed.hash,
+        signed_action: record.signed_action,
+      },
+    });
+    const legacy = await waitForSignal(aliceSignals, signal => signal.type === "Message");
+    assert.ok(legacy);
+    assert.equal(legacy.message.content, entryOf(record).content);
+
+    // Newer agents may add fields to known kinds, which are ignored
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: {
+        protocol_version: 3,
+        signal: { kind: "typing", typing: "Started", sent_at: Date.now() * 1000, mood: "excited" },
+      },
+    });
+    const typing = await waitForSignal(aliceSignals, signal => signal.type === "Typing");
+    assert.ok(typing);
+    assert.equal(typing.state, "Started");
+
+    // and kinds this version doesn't know about are dropped without an error
+    const isRemote = (signal: any) => !/^(Entry|Link)/.test(signal.type);
+    const signalCount = aliceSignals.filter(isRemote).length;
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: {
+        protocol_version: 3,
+        signal: { kind: "future_kind", payload: "from the future" },
+      },
+    });
+    await pause(1000);
+    assert.equal(aliceSignals.filter(isRemote).length, signalCount);
+  });
+});
@@ -0,0 +1,165 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createConversation, entryOf, generateMembraneProof, joinConversation, Privacy, sampleConversationMessage, sendMessage } from './common.js';
+
+test('replies form threads which can be paged through', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+
+    // Alice posts, Bob replies, and Alice replies to Bob
+    const root: Record = await sendMessage(aliceCell);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    const reply: Record = await sendMessage(bobCell, sampleConversationMessage({
+      content: "Reply",
+      reply_to: root.signed_action.hashed.hash,
+    }));
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    await sendMessage(aliceCell, sampleConversationMessage({
+      content: "Reply to the reply",
+      reply_to: reply.signed_action.hashed.hash,
+    }));
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const replies: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_replies",
+      payload: { parent: root.signed_action.hashed.hash, offset: 0, limit: 10 },
+    });
+    assert.equal(replies.total, 1);
+    assert.equal(replies.messages[0].message.content, "Reply");
+
+    const thread: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_thread",
+      payload: { root: root.signed_action.hashed.hash, offset: 0, limit: 10 },
+    });
+    assert.equal(thread.total, 2);
+    assert.isFalse(thread.truncated);
+    assert.deepEqual(thread.messages.map((m: any) => m.message.content), ["Reply", "Reply to the reply"]);
+
+    const secondPage: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_thread",
+      payload: { root: root.signed_action.hashed.hash, offset: 1, limit: 1 },
+    });
+    assert.equal(secondPage.total, 2);
+    assert.deepEqual(secondPage.messages.map((m: any) => m.message.content), ["Reply to the reply"]);
+
+    // An edit cannot move a reply to another parent
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: reply.signed_action.hashed.hash,
+        previous_message_hash: reply.signed_action.hashed.hash,
+        updated_message: { ...entryOf(reply), reply_to: undefined },
+      },
+    })).rejects.toThrow(/Message reply_to cannot be changed by an update/);
+  });
+});
+
+test('replies must reference an original Message', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    const [, aliceCell] = await createConversation(alice, Privacy.Public);
+
+    // Alice posts and edits a message
+    const root: Record = await sendMessage(aliceCell);
+    const edit: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: root.signed_action.hashed.hash,
+        previous_message_hash: root.signed_action.hashed.hash,
+        updated_message: { ...entryOf(root), content: "Edited" },
+      },
+    });
+
+    // Replies point at the original message, not at one of its revisions
+    await expect(sendMessage(aliceCell, sampleConversationMessage({
+      reply_to: edit.signed_action.hashed.hash,
+    }))).rejects.toThrow(/Replies must reference the original Message/);
+  });
+});
+
+test('reply totals leave out replies hidden from revoked members', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobProof = await generateMembraneProof(aliceCell, conversation, bob.agentPubKey);
+    const bobCell = await joinConversation(bob, conversation, bobProof);
+
+    // Bob and Alice both reply to Alice's message
+    const root: Record = await sendMessage(aliceCell);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    await sendMessage(bobCell, sampleConversationMessage({
+      content: "Bob's reply",
+      reply_to: root.signed_action.hashed.hash,
+    }));
+    await sendMessage(aliceCell, sampleConversationMessage({
+      content: "Alice's reply",
+      reply_to: root.signed_action.hashed.hash,
+    }));
+
+    // Alice revokes Bob's proof, which hides his reply
+    const bobEnvelope: any = decode(bobProof);
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "revoke_membrane_proof",
+      payload: { for_agent: bob.agentPubKey, signature: bobEnvelope.signature },
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const replies: any = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_replies",
+      payload: { parent: root.signed_action.hashed.hash, offset: 0, limit: 10 },
+    });
+    assert.equal(replies.total, 1);
+    assert.deepEqual(replies.messages.map((m: any) => m.message.content), ["Alice's reply"]);
+  });
+});
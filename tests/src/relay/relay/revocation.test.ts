@@ -0,0 +1,108 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { decode } from '@msgpack/msgpack';
+
+import { createConversation, generateMembraneProof, inviteToConversation, joinConversation, Privacy, requestAdmission, sendMessage } from './common.js';
+
+const MINUTE_US = 1000 * 1000 * 60;
+
+test('membrane proofs can only be used within their validity window', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const now = Date.now() * 1000;
+
+    // Bob's proof has expired
+    const expiredProof = await generateMembraneProof(aliceCell, conversation, bob.agentPubKey, {
+      valid_until: now - MINUTE_US,
+    });
+    await expect(joinConversation(bob, conversation, expiredProof)).rejects.toThrow();
+
+    // Carol's proof is not valid yet
+    const futureProof = await generateMembraneProof(aliceCell, conversation, carol.agentPubKey, {
+      valid_from: now + 60 * MINUTE_US,
+    });
+    await expect(joinConversation(carol, conversation, futureProof)).rejects.toThrow();
+
+    // A proof for Carol cannot be used by Bob
+    const carolProof = await generateMembraneProof(aliceCell, conversation, carol.agentPubKey);
+    await expect(joinConversation(bob, conversation, carolProof)).rejects.toThrow();
+
+    // Carol joins with a proof valid now, and is a guest until Alice admits her
+    const carolCell = await joinConversation(carol, conversation, carolProof);
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Guest");
+    await requestAdmission(carolCell);
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Member");
+  });
+});
+
+test('agents whose membrane proof was revoked before they were admitted stay guests', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation and invites Bob
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    assert.equal(await bobCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Member");
+
+    // Bob is a member, not the signer of Carol's proof, so he cannot revoke it
+    const carolProof = await generateMembraneProof(aliceCell, conversation, carol.agentPubKey);
+    const carolEnvelope: any = decode(carolProof);
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "revoke_membrane_proof",
+      payload: { for_agent: carol.agentPubKey, signature: carolEnvelope.signature },
+    })).rejects.toThrow(/Only the owner or the signer of a membrane proof can revoke it/);
+
+    // Alice revokes it before Carol joins
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "revoke_membrane_proof",
+      payload: { for_agent: carol.agentPubKey, signature: carolEnvelope.signature },
+    });
+
+    // Carol passes genesis, but Alice refuses to admit her
+    const carolCell = await joinConversation(carol, conversation, carolProof);
+    await expect(requestAdmission(carolCell)).rejects.toThrow(/Membrane proof was revoked before joining/);
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Guest");
+    await expect(sendMessage(carolCell)).rejects.toThrow(/Only members can post messages/);
+
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    const isRevoked: boolean = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "is_agent_revoked",
+      payload: carol.agentPubKey,
+    });
+    assert.ok(isRevoked);
+  });
+});
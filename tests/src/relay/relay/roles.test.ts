@@ -0,0 +1,69 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { createConversation, generateMembraneProof, inviteToConversation, MembraneProofRole, Privacy, sendMessage } from './common.js';
+
+test('roles come from the membrane proof each agent joined with', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation, inviting Bob as a guest and Carol as an admin
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob, { as_role: MembraneProofRole.Guest });
+    const carolCell = await inviteToConversation(aliceCell, conversation, carol, { as_role: MembraneProofRole.Admin });
+
+    assert.equal(await aliceCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Owner");
+    assert.equal(await bobCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Guest");
+    assert.equal(await carolCell.callZome({ zome_name: "relay", fn_name: "get_my_role", payload: null }), "Admin");
+
+    // Guests can read but not post
+    await expect(sendMessage(bobCell)).rejects.toThrow(/Only members can post messages/);
+
+    // Admins can delete messages authored by other agents
+    const record: Record = await sendMessage(aliceCell);
+    await dhtSync([alice, carol], aliceCell.cell_id[0]);
+    const deleteActionHash = await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "delete_message",
+      payload: record.signed_action.hashed.hash,
+    });
+    assert.ok(deleteActionHash);
+  });
+});
+
+test('membrane proofs cannot grant an unknown role', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Alice creates a private conversation
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+
+    await expect(
+      generateMembraneProof(aliceCell, conversation, bob.agentPubKey, { as_role: 7 }),
+    ).rejects.toThrow(/unknown_role/);
+  });
+});
@@ -0,0 +1,57 @@
+import { assert, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+import { Record } from '@holochain/client';
+
+import { createConversation, entryOf, Privacy, sampleConversationMessage, sendMessage } from './common.js';
+
+test('entries are written with their current schema version', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    const [, aliceCell] = await createConversation(alice, Privacy.Public);
+
+    // The version sent by the client is replaced with the one this coordinator writes
+    const message: Record = await sendMessage(aliceCell, sampleConversationMessage({ schema_version: 99 }));
+    assert.equal(entryOf(message).schema_version, 2);
+
+    const contact: Record = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "create_contact",
+      payload: {
+        public_key: alice.agentPubKey,
+        first_name: "Alice",
+        last_name: "Liddell",
+        avatar: "",
+      },
+    });
+    assert.equal(entryOf(contact).schema_version, 1);
+
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "set_config",
+      payload: { title: "Versioned", image: "" },
+    });
+    const config: any = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_config",
+      payload: null,
+    });
+    assert.equal(config.schema_version, 1);
+
+    // Reads return messages upgraded to the current version
+    const latest: any = await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_latest_message",
+      payload: message.signed_action.hashed.hash,
+    });
+    assert.equal(latest.message.schema_version, 2);
+  });
+});
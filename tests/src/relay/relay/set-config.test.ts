@@ -0,0 +1,58 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from '@holochain/tryorama';
+
+import { createConversation, inviteToConversation, MembraneProofRole, Privacy } from './common.js';
+
+test('only admins can set the Config', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a private conversation, inviting Bob as a member and Carol as an admin
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Private);
+    const bobCell = await inviteToConversation(aliceCell, conversation, bob);
+    const carolCell = await inviteToConversation(aliceCell, conversation, carol, { as_role: MembraneProofRole.Admin });
+
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "set_config",
+      payload: { title: "Set by Alice", image: "" },
+    });
+
+    // Bob is a member, so he cannot change the Config
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "set_config",
+      payload: { title: "Set by Bob", image: "" },
+    })).rejects.toThrow(/unauthorized/);
+
+    // Carol is an admin, so she can
+    await carolCell.callZome({
+      zome_name: "relay",
+      fn_name: "set_config",
+      payload: { title: "Set by Carol", image: "" },
+    });
+
+    await dhtSync([alice, bob, carol], aliceCell.cell_id[0]);
+
+    const config: any = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "get_config",
+      payload: null,
+    });
+    assert.equal(config.title, "Set by Carol");
+  });
+});
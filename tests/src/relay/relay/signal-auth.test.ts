@@ -0,0 +1,261 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync, pause } from '@holochain/tryorama';
+import { ActionHash, Record, encodeHashToBase64 } from '@holochain/client';
+
+import { collectSignals, createConversation, entryOf, joinConversation, Privacy, sendMessage, waitForSignal } from './common.js';
+
+// Only an agent's own conductor can sign as them, so each agent delivers remote signals to their own cell,
+// forging everything except the provenance
+function relaySignal(signal: any) {
+  return { protocol_version: 2, signal };
+}
+
+function messageSignal(kind: string, record: Record, originalAction: ActionHash, message = entryOf(record)) {
+  return relaySignal({
+    kind,
+    message,
+    original_action: originalAction,
+    signed_action: record.signed_action,
+  });
+}
+
+// Signals sent by the zome itself, rather than relayed from remote signals
+function isRemote(signal: any): boolean {
+  return !/^(Entry|Link)/.test(signal.type);
+}
+
+test('message signals must carry a message signed by the sender', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins, and both post a message
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    const aliceRecord: Record = await sendMessage(aliceCell);
+    const bobRecord: Record = await sendMessage(bobCell);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    const bobSignals = collectSignals(bob, bobCell);
+
+    const receive = (payload: any) => bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload,
+    });
+
+    // Bob cannot pass off Alice's message as his own
+    await receive(messageSignal("message", aliceRecord, aliceRecord.signed_action.hashed.hash));
+    // nor change the content of a message he signed
+    await receive(messageSignal("message", bobRecord, bobRecord.signed_action.hashed.hash, {
+      ...entryOf(bobRecord),
+      content: "Not what Bob wrote",
+    }));
+    // nor claim it is another message
+    await receive(messageSignal("message", bobRecord, aliceRecord.signed_action.hashed.hash));
+    await pause(1000);
+    assert.isFalse(bobSignals.some(signal => signal.type === "Message"));
+
+    // The message as Bob signed it is accepted
+    await receive(messageSignal("message", bobRecord, bobRecord.signed_action.hashed.hash));
+    const received = await waitForSignal(bobSignals, signal => signal.type === "Message");
+    assert.ok(received);
+    assert.equal(received.message.content, entryOf(bobRecord).content);
+    assert.deepEqual(received.from, bob.agentPubKey);
+  });
+});
+
+test('edit signals must carry an edit of a message by the sender', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins, and both post a message
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    const aliceRecord: Record = await sendMessage(aliceCell);
+    const bobRecord: Record = await sendMessage(bobCell);
+    const originalActionHash = bobRecord.signed_action.hashed.hash;
+
+    // Bob edits his message twice
+    const firstEdit: Record = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: originalActionHash,
+        previous_message_hash: originalActionHash,
+        updated_message: { ...entryOf(bobRecord), content: "First edit" },
+      },
+    });
+    const secondEdit: Record = await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "update_message",
+      payload: {
+        original_message_hash: originalActionHash,
+        previous_message_hash: firstEdit.signed_action.hashed.hash,
+        updated_message: { ...entryOf(bobRecord), content: "Second edit" },
+      },
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    const bobSignals = collectSignals(bob, bobCell);
+
+    const receive = (payload: any) => bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload,
+    });
+
+    // Bob cannot claim his edit changes Alice's message
+    await receive(messageSignal("message_updated", secondEdit, aliceRecord.signed_action.hashed.hash));
+    // nor change what the edit says
+    await receive(messageSignal("message_updated", secondEdit, originalActionHash, {
+      ...entryOf(secondEdit),
+      content: "Not what Bob wrote",
+    }));
+    // nor pass off a new message as an edit
+    await receive(messageSignal("message_updated", bobRecord, originalActionHash));
+    await pause(1000);
+    assert.isFalse(bobSignals.some(signal => signal.type === "MessageUpdated"));
+
+    // Edits of edits are traced back to the original message
+    await receive(messageSignal("message_updated", secondEdit, originalActionHash));
+    const updated = await waitForSignal(bobSignals, signal => signal.type === "MessageUpdated");
+    assert.ok(updated);
+    assert.deepEqual(updated.original_action, originalActionHash);
+    assert.equal(updated.message.content, "Second edit");
+  });
+});
+
+test('delete signals must target a message by the sender', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins, and both post two messages
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    const aliceSignals = collectSignals(alice, aliceCell);
+    const bobSignals = collectSignals(bob, bobCell);
+    const aliceRecord: Record = await sendMessage(aliceCell);
+    const bobRecord: Record = await sendMessage(bobCell);
+    const bobOtherRecord: Record = await sendMessage(bobCell);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    const deleteOf = async (cell: typeof aliceCell, signals: any[], record: Record) => {
+      const messageHash = record.signed_action.hashed.hash;
+      await cell.callZome({
+        zome_name: "relay",
+        fn_name: "delete_message",
+        payload: messageHash,
+      });
+      const deleted = await waitForSignal(signals, signal =>
+        signal.type === "EntryDeleted"
+        && encodeHashToBase64(signal.action.hashed.content.deletes_address) === encodeHashToBase64(messageHash));
+      return deleted.action;
+    };
+
+    // Alice moderates Bob's message
+    const moderation = await deleteOf(aliceCell, aliceSignals, bobOtherRecord);
+    const bobDelete = await deleteOf(bobCell, bobSignals, bobRecord);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    // Moderators don't announce the messages they delete as if they were the author
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: relaySignal({
+        kind: "message_deleted",
+        message: bobOtherRecord.signed_action.hashed.hash,
+        delete: moderation,
+      }),
+    });
+    // and Bob cannot claim his delete removed Alice's message
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: relaySignal({
+        kind: "message_deleted",
+        message: aliceRecord.signed_action.hashed.hash,
+        delete: bobDelete,
+      }),
+    });
+    await pause(1000);
+    assert.isFalse(aliceSignals.some(signal => signal.type === "MessageDeleted"));
+    assert.isFalse(bobSignals.some(signal => signal.type === "MessageDeleted"));
+
+    // Bob deleting his own message is announced
+    await bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: relaySignal({
+        kind: "message_deleted",
+        message: bobRecord.signed_action.hashed.hash,
+        delete: bobDelete,
+      }),
+    });
+    const deleted = await waitForSignal(bobSignals, signal => signal.type === "MessageDeleted");
+    assert.ok(deleted);
+    assert.deepEqual(deleted.message, bobRecord.signed_action.hashed.hash);
+  });
+});
+
+test('remote signals are dropped outside of a conversation', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 1 player with the test app to the Scenario
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // The provisioned cell has no conversation properties, so nobody is a member of it
+    const cell = alice.cells[0];
+    const signals = collectSignals(alice, cell);
+    await cell.callZome({
+      zome_name: "relay",
+      fn_name: "recv_remote_signal",
+      payload: relaySignal({ kind: "typing", typing: "Started", sent_at: Date.now() * 1000 }),
+    });
+    await pause(1000);
+    assert.isFalse(signals.some(isRemote));
+  });
+});
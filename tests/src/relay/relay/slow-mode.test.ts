@@ -0,0 +1,35 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario } from '@holochain/tryorama';
+
+import { createConversation, joinConversation, Privacy, sendMessage } from './common.js';
+
+test('slow mode limits how often members can post', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Alice creates a public conversation allowing 2 messages a minute, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public, {
+      limits: { slow_mode: { min_interval_ms: 60000, burst: 2 } },
+    });
+    const bobCell = await joinConversation(bob, conversation);
+
+    assert.ok(await sendMessage(bobCell));
+    assert.ok(await sendMessage(bobCell));
+    await expect(sendMessage(bobCell)).rejects.toThrow(/Slow mode allows at most 2 messages every 60000 ms/);
+
+    // Alice owns the conversation, so slow mode does not apply to her
+    for (let i = 0; i < 3; i++) {
+      assert.ok(await sendMessage(aliceCell));
+    }
+  });
+});
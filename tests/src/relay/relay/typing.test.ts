@@ -0,0 +1,91 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, pause } from '@holochain/tryorama';
+
+import { collectSignals, createConversation, joinConversation, Privacy, waitForSignal } from './common.js';
+
+test('members are notified when someone is typing', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+    const aliceSignals = collectSignals(alice, aliceCell);
+    const bobSignals = collectSignals(bob, bobCell);
+
+    // Alice starts typing, and lists herself among the members
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "notify_typing",
+      payload: { state: "Started", agents: [alice.agentPubKey, bob.agentPubKey] },
+    });
+    const started = await waitForSignal(bobSignals, signal => signal.type === "Typing" && signal.state === "Started");
+    assert.ok(started);
+    assert.deepEqual(started.from, alice.agentPubKey);
+    assert.isAbove(started.expires_at, Date.now() * 1000);
+
+    // Alice stops typing, which hides the indicator right away
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "notify_typing",
+      payload: { state: "Stopped", agents: [bob.agentPubKey] },
+    });
+    const stopped = await waitForSignal(bobSignals, signal => signal.type === "Typing" && signal.state === "Stopped");
+    assert.ok(stopped);
+    assert.isAtMost(stopped.expires_at, Date.now() * 1000);
+
+    // The sender is never notified of her own typing
+    await pause(1000);
+    assert.isFalse(aliceSignals.some(signal => signal.type === "Typing"));
+  });
+});
+
+test('nobody can type in a frozen conversation', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/relay.happ';
+
+    // Set up the app to be installed
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a public conversation, which Bob joins, and freezes it
+    const [conversation, aliceCell] = await createConversation(alice, Privacy.Public);
+    const bobCell = await joinConversation(bob, conversation);
+    await aliceCell.callZome({
+      zome_name: "relay",
+      fn_name: "freeze_conversation",
+      payload: { reason: "Archived" },
+    });
+    await dhtSync([alice, bob], aliceCell.cell_id[0]);
+
+    await expect(bobCell.callZome({
+      zome_name: "relay",
+      fn_name: "notify_typing",
+      payload: { state: "Started", agents: [alice.agentPubKey] },
+    })).rejects.toThrow(/conversation_closed/);
+  });
+});
@@ -1,7 +1,9 @@
 // Minimum length of a custom conversation title
 export const MIN_TITLE_LENGTH = 3;
 
-// Timestamp range of actions contained within a single bucket, in milliseconds
+// Timestamp range of actions contained within a single bucket, in milliseconds,
+// used when not set in the conversation's DNA properties.
+// Mirror of rust constant DEFAULT_BUCKET_RANGE_MS
 export const BUCKET_RANGE_MS = 1000 * 60 * 60 * 24; // 1 day
 
 // Target number of messages to load in a single request for additional message history
@@ -21,6 +23,9 @@ export const MESSAGE_MAX_FILENAME_LENGTH = 20;
 // Maximum filename length to display in ConversationMessageInput
 export const INPUT_MAX_FILENAME_LENGTH = 10;
 
+// Minimum time between two "Started" typing notifications sent to a conversation, enforced by receivers too
+export const TYPING_RATE_LIMIT_MS = 2 * 1000;
+
 export const ROLE_NAME = "relay";
 export const ZOME_NAME = "relay";
 
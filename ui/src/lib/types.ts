@@ -27,8 +27,74 @@ export interface MessageSignal {
   from: AgentPubKey;
 }
 
+export interface ReplySignal {
+  type: "Reply";
+  parent: ActionHash;
+  action: SignedActionHashed<Create>;
+  message: Message;
+  from: AgentPubKey;
+}
+
+export interface ReactionSignal {
+  type: "Reaction";
+  message: ActionHash;
+  emoji: string;
+  removed: boolean;
+  from: AgentPubKey;
+}
+
+export interface ReadReceipt {
+  conversation_id: string;
+  last_read: ActionHash;
+  last_read_at: number;
+}
+
+export interface ReadReceiptSignal {
+  type: "ReadReceipt";
+  receipt: ReadReceipt;
+  from: AgentPubKey;
+}
+
+export interface DeliveredSignal {
+  type: "Delivered";
+  message: ActionHash;
+  from: AgentPubKey;
+}
+
+export type TypingState = "Started" | "Stopped";
+
+export interface TypingSignal {
+  type: "Typing";
+  state: TypingState;
+  // Hide the indicator at this time, in microseconds, unless another TypingSignal renews it
+  expires_at: number;
+  from: AgentPubKey;
+}
+
+export interface MessageUpdatedSignal {
+  type: "MessageUpdated";
+  original_action: ActionHash;
+  action: SignedActionHashed<Update>;
+  message: Message;
+  from: AgentPubKey;
+}
+
+export interface MessageDeletedSignal {
+  type: "MessageDeleted";
+  message: ActionHash;
+  action: SignedActionHashed<Delete>;
+  from: AgentPubKey;
+}
+
 export type RelaySignal =
   | MessageSignal
+  | MessageUpdatedSignal
+  | MessageDeletedSignal
+  | ReplySignal
+  | ReactionSignal
+  | ReadReceiptSignal
+  | DeliveredSignal
+  | TypingSignal
   | {
       type: "EntryCreated";
       action: SignedActionHashed<Create>;
@@ -77,6 +143,12 @@ export interface Message {
   content: string;
   bucket: number;
   images: MessageFile[];
+  // Set by the zome to the conversation status the entry cites
+  status?: ActionHash;
+  // The message this one replies to
+  reply_to?: ActionHash;
+  // Set by the relay zome when writing, 0 or missing for entries written before schema versions
+  schema_version?: number;
 }
 
 export interface MessageExtended {
@@ -91,6 +163,48 @@ export interface MessageRecord {
   message?: Message;
 }
 
+export interface MessagePage {
+  messages: MessageRecord[];
+  total: number;
+  // Whether getThread stopped walking the thread early, so some replies may be missing
+  truncated: boolean;
+}
+
+export interface ReactionInput {
+  message: ActionHash;
+  emoji: string;
+  agents: AgentPubKey[];
+}
+
+export interface Reaction {
+  emoji: string;
+  agent: AgentPubKey;
+  timestamp: number;
+}
+
+export interface MessageReactions {
+  message: ActionHash;
+  reactions: Reaction[];
+}
+
+export interface ReadMarker {
+  last_read: ActionHash;
+  last_read_at: number;
+}
+
+export interface MessageReader {
+  agent: AgentPubKey;
+  read_at: number;
+}
+
+export type DeliveryStatus = "Sent" | "Delivered" | "Failed";
+
+export interface RecipientDeliveryStatus {
+  agent: AgentPubKey;
+  status: DeliveryStatus;
+  attempts: number;
+}
+
 export interface SendMessageInput {
   message: Message;
   agents: AgentPubKey[];
@@ -103,6 +217,10 @@ export interface SendMessageInput {
 export interface Config {
   title: string;
   image: string;
+  // Set by the zome to the conversation status the entry cites
+  status?: ActionHash;
+  // Set by the relay zome when writing, 0 or missing for entries written before schema versions
+  schema_version?: number;
 }
 
 export enum Privacy {
@@ -147,6 +265,30 @@ export interface RelayDnaProperties {
   //
   // See https://github.com/holochain-apps/volla-messages/issues/392
   progenitor: AgentPubKeyB64;
+
+  // Optional, the DNA falls back to default limits when missing
+  limits?: RelayDnaLimits;
+
+  // Optional, the DNA falls back to DEFAULT_BUCKET_RANGE_MS when missing
+  bucket_range_ms?: number;
+
+  // Optional end date in milliseconds, after which the conversation is read-only
+  ends_at_ms?: number;
+}
+
+// Mirror of rust type Limits
+export interface RelayDnaLimits {
+  max_content_length: number;
+  max_attachments: number;
+  max_attachment_size: number;
+  allowed_file_types: string[];
+  slow_mode?: RelaySlowMode;
+}
+
+// Mirror of rust type SlowMode
+export interface RelaySlowMode {
+  min_interval_ms: number;
+  burst: number;
 }
 
 export interface Invitation {
@@ -158,10 +300,48 @@ export interface Invitation {
   proof?: MembraneProof;
 }
 
+// Mirror of rust constants ROLE_MEMBER, ROLE_ADMIN, ROLE_GUEST
+export enum MembraneProofRole {
+  Member = 0,
+  Admin = 1,
+  Guest = 2,
+}
+
 export interface MembraneProofData {
   conversation_id: string;
   for_agent: AgentPubKey;
-  as_role: number;
+  as_role: MembraneProofRole;
+
+  // Optional validity window, in microseconds
+  valid_from?: number;
+  valid_until?: number;
+
+  // Set by the signer to the head of their source chain
+  issued_after?: ActionHash;
+}
+
+export interface InviteDelegationEnvelope {
+  signature: Uint8Array;
+  data: {
+    conversation_id: string;
+    delegate: AgentPubKey;
+  };
+}
+
+export interface GenerateInviteTokenInput {
+  // Raw ed25519 public key of the token's secret key, as an array of bytes
+  key: number[];
+  max_uses: number;
+  // Timestamp in microseconds
+  expires_at: number;
+  as_role: MembraneProofRole;
+}
+
+// An invite token, and the secret key its holders sign their public key with to join
+export interface InviteTokenSecret {
+  token: MembraneProof;
+  // PKCS #8 encoded ed25519 secret key
+  secret_key: Uint8Array;
 }
 
 /**
@@ -173,6 +353,8 @@ export interface Contact {
   first_name: string;
   last_name: string;
   avatar: string;
+  // Set by the relay zome when writing, 0 or missing for entries written before schema versions
+  schema_version?: number;
 }
 
 export interface ContactRecord {
@@ -253,3 +435,23 @@ export interface FileExtended {
   file?: File;
   status: FileStatus;
 }
+
+// Mirror of rust type RelayError, serialized as JSON in the message of errors from the relay zome
+export type RelayError =
+  | { code: "not_found"; kind: string; hash?: string }
+  | { code: "malformed_record"; kind: string; reason: string }
+  | { code: "invalid_link_target"; link_type: string }
+  | { code: "unauthorized"; action: string }
+  | { code: "unknown_role"; role: number }
+  | { code: "conversation_closed"; closed_at: number }
+  | { code: "handover_not_pending"; hash: string }
+  | { code: "handover_not_expired"; hash: string }
+  | { code: "admission_refused"; reason: string };
+
+// Mirror of rust type ApiInfo, returned by the relay zome's get_api_info
+export interface RelayApiInfo {
+  coordinator_version: string;
+  integrity_schema_version: number;
+  remote_signal_protocol_version: number;
+  features: string[];
+}
@@ -1,5 +1,10 @@
 import { decode, encode } from "@msgpack/msgpack";
-import { decodeHashFromBase64, type AgentPubKeyB64, type ClonedCell } from "@holochain/client";
+import {
+  decodeHashFromBase64,
+  type AgentPubKeyB64,
+  type CellId,
+  type ClonedCell,
+} from "@holochain/client";
 import {
   Privacy,
   type CellIdB64,
@@ -20,6 +25,7 @@ import {
 import { get } from "svelte/store";
 import { persisted } from "./generic/GenericPersistedStore";
 import { Base64 } from "js-base64";
+import pRetry from "p-retry";
 import { BUCKET_RANGE_MS } from "$config";
 
 export interface ConversationStore extends GenericKeyValueStore<ConversationExtended> {
@@ -85,6 +91,7 @@ export function createConversationStore(client: RelayClient): ConversationStore
   async function join(input: Invitation): Promise<CellIdB64> {
     const cellInfo = await client.joinConversation(input);
     await client.setMyProfileForConversation(cellInfo.cell_id);
+    if (input.privacy === Privacy.Private) _requestAdmission(cellInfo.cell_id);
 
     const conversationExtended = await _makeConversationExtended(cellInfo);
     const cellIdB64 = encodeCellIdToBase64(cellInfo.cell_id);
@@ -96,6 +103,29 @@ export function createConversationStore(client: RelayClient): ConversationStore
     return cellIdB64;
   }
 
+  // Members of a private conversation are guests until the signer of their proof admits them,
+  // so keep asking in the background until the signer comes online
+  async function _requestAdmission(cellId: CellId): Promise<void> {
+    try {
+      await pRetry(
+        async () => {
+          if (!(await client.requestAdmission(cellId))) throw new Error("Signer not reachable");
+        },
+        {
+          retries: 10,
+          minTimeout: 1000,
+          maxTimeout: 60000,
+          factor: 2,
+          onFailedAttempt: (e) => {
+            console.error(`Failed attempt ${e.attemptNumber} to request admission`, e);
+          },
+        },
+      );
+    } catch (e) {
+      console.error("Failed to request admission, giving up.", e);
+    }
+  }
+
   async function enable(key: CellIdB64): Promise<void> {
     await client.enableConversationCell(decodeCellIdFromBase64(key));
     await initialize();
@@ -182,7 +212,8 @@ export function createConversationStore(client: RelayClient): ConversationStore
     const c = get(conversations).data[key1];
     if (!c) throw new Error(`Failed to get conversation with CellIdB64 ${key1}`);
 
-    return Math.round((timestamp - c.dnaProperties.created) / BUCKET_RANGE_MS);
+    const bucketRangeMs = c.dnaProperties.bucket_range_ms ?? BUCKET_RANGE_MS;
+    return Math.round((timestamp - c.dnaProperties.created) / bucketRangeMs);
   }
 
   async function _makeConversationExtended(cellInfo: ClonedCell): Promise<ConversationExtended> {
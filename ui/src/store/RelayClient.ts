@@ -1,4 +1,5 @@
 import { v4 as uuidv4 } from "uuid";
+import { decode, encode } from "@msgpack/msgpack";
 import {
   CellType,
   encodeHashToBase64,
@@ -20,17 +21,31 @@ import type {
   Invitation,
   MembraneProofData,
   MessageRecord,
+  MessagePage,
+  MessageReactions,
+  MessageReader,
+  RecipientDeliveryStatus,
+  TypingState,
+  ReadMarker,
   UpdateContactInput,
   Profile,
   ProfileExtended,
   BucketInput,
   CreateConversationInput,
   SendMessageInput,
+  GenerateInviteTokenInput,
+  InviteTokenSecret,
+  InviteDelegationEnvelope,
+  RelayApiInfo,
 } from "$lib/types";
-import { ZOME_NAME, ROLE_NAME } from "$config";
+import { MembraneProofRole } from "$lib/types";
+import { ZOME_NAME, ROLE_NAME, TYPING_RATE_LIMIT_MS } from "$config";
 import { encodeCellIdToBase64 } from "$lib/utils";
 
 export class RelayClient {
+  // When "Started" was last sent to each conversation, keyed by CellIdB64
+  private typingSentAt: { [key: string]: number } = {};
+
   constructor(
     public client: AppClient,
     public provisionedRelayCellId: CellId,
@@ -159,6 +174,16 @@ export class RelayClient {
     return cellInfo;
   }
 
+  // Ask the signer of my membrane proof to admit me, returns false while they cannot be reached
+  public async requestAdmission(cell_id: CellId): Promise<boolean> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "request_admission",
+      payload: null,
+    });
+  }
+
   public async getMessageHashes(cell_id: CellId, payload: BucketInput): Promise<Array<ActionHash>> {
     return this.client.callZome({
       cell_id,
@@ -231,7 +256,7 @@ export class RelayClient {
       fn_name: "get_config",
       payload: null,
     });
-    return config ? new EntryRecord<Config>(config).entry : undefined;
+    return config ?? undefined;
   }
 
   public async createMessage(cell_id: CellId, payload: SendMessageInput): Promise<Record> {
@@ -267,7 +292,7 @@ export class RelayClient {
   public async generateMembraneProof(
     cell_id: CellId,
     forAgent: AgentPubKey,
-    role: number = 0,
+    role: MembraneProofRole = MembraneProofRole.Member,
   ): Promise<MembraneProof> {
     const relayClonedCellInfos = await this.getRelayClonedCellInfos();
     const conversation = relayClonedCellInfos.find(
@@ -290,6 +315,277 @@ export class RelayClient {
     return r;
   }
 
+  /**
+   * Generate a multi-use invite token, which anyone holding it and its secret key can use to join
+   */
+  public async generateInviteToken(
+    cell_id: CellId,
+    input: Omit<GenerateInviteTokenInput, "key">,
+  ): Promise<InviteTokenSecret> {
+    const keyPair = (await crypto.subtle.generateKey({ name: "Ed25519" }, true, [
+      "sign",
+      "verify",
+    ])) as CryptoKeyPair;
+    const key = Array.from(new Uint8Array(await crypto.subtle.exportKey("raw", keyPair.publicKey)));
+    const token = await this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "generate_invite_token",
+      payload: { ...input, key },
+    });
+    const secret_key = new Uint8Array(await crypto.subtle.exportKey("pkcs8", keyPair.privateKey));
+
+    return { token, secret_key };
+  }
+
+  /**
+   * Sign my public key with the secret key of an invite token, giving the membrane proof to join with
+   */
+  public async signInviteToken(invite: InviteTokenSecret): Promise<MembraneProof> {
+    const secretKey = await crypto.subtle.importKey(
+      "pkcs8",
+      invite.secret_key,
+      { name: "Ed25519" },
+      false,
+      ["sign"],
+    );
+    const agent_signature = new Uint8Array(
+      await crypto.subtle.sign({ name: "Ed25519" }, secretKey, this.client.myPubKey),
+    );
+    const envelope = decode(invite.token) as object;
+
+    return encode({ ...envelope, agent_signature });
+  }
+
+  public async grantInviteAuthority(cell_id: CellId, delegate: AgentPubKey): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "grant_invite_authority",
+      payload: delegate,
+    });
+  }
+
+  public async revokeInviteDelegation(
+    cell_id: CellId,
+    delegation: InviteDelegationEnvelope,
+  ): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "revoke_invite_delegation",
+      payload: delegation,
+    });
+  }
+
+  public async proposeOwnershipHandover(cell_id: CellId, successor: AgentPubKey): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "propose_ownership_handover",
+      payload: successor,
+    });
+  }
+
+  public async cancelOwnershipHandover(cell_id: CellId, handoverHash: ActionHash): Promise<ActionHash> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "cancel_ownership_handover",
+      payload: handoverHash,
+    });
+  }
+
+  public async acceptOwnershipHandover(cell_id: CellId, handoverHash: ActionHash): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "accept_ownership_handover",
+      payload: handoverHash,
+    });
+  }
+
+  public async reclaimOwnership(cell_id: CellId, handoverHash: ActionHash): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "reclaim_ownership",
+      payload: handoverHash,
+    });
+  }
+
+  public async getCurrentOwner(cell_id: CellId): Promise<AgentPubKey | null> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_current_owner",
+      payload: null,
+    });
+  }
+
+  public async freezeConversation(cell_id: CellId, reason: string): Promise<Record> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "freeze_conversation",
+      payload: { reason },
+    });
+  }
+
+  public async getConversationClosedAt(cell_id: CellId): Promise<number | null> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_conversation_closed_at",
+      payload: null,
+    });
+  }
+
+  public async getApiInfo(cell_id: CellId): Promise<RelayApiInfo> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_api_info",
+      payload: null,
+    });
+  }
+
+  public async getReplies(
+    cell_id: CellId,
+    parent: ActionHash,
+    offset: number,
+    limit: number,
+  ): Promise<MessagePage> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_replies",
+      payload: { parent, offset, limit },
+    });
+  }
+
+  public async getThread(
+    cell_id: CellId,
+    root: ActionHash,
+    offset: number,
+    limit: number,
+  ): Promise<MessagePage> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_thread",
+      payload: { root, offset, limit },
+    });
+  }
+
+  public async addReaction(
+    cell_id: CellId,
+    message: ActionHash,
+    emoji: string,
+    agents: AgentPubKey[],
+  ): Promise<ActionHash> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "add_reaction",
+      payload: { message, emoji, agents },
+    });
+  }
+
+  public async removeReaction(
+    cell_id: CellId,
+    message: ActionHash,
+    emoji: string,
+    agents: AgentPubKey[],
+  ): Promise<void> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "remove_reaction",
+      payload: { message, emoji, agents },
+    });
+  }
+
+  public async getReactionsForMessages(
+    cell_id: CellId,
+    messages: ActionHash[],
+  ): Promise<MessageReactions[]> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_reactions_for_messages",
+      payload: messages,
+    });
+  }
+
+  public async markRead(
+    cell_id: CellId,
+    message: ActionHash,
+    share: boolean,
+    agents: AgentPubKey[],
+  ): Promise<ReadMarker> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "mark_read",
+      payload: { message, share, agents },
+    });
+  }
+
+  public async getReadState(cell_id: CellId): Promise<ReadMarker | null> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_read_state",
+      payload: null,
+    });
+  }
+
+  public async getReadersForMessage(
+    cell_id: CellId,
+    message: ActionHash,
+  ): Promise<MessageReader[]> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_readers_for_message",
+      payload: message,
+    });
+  }
+
+  public async getDeliveryStatus(
+    cell_id: CellId,
+    message: ActionHash,
+  ): Promise<RecipientDeliveryStatus[]> {
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "get_delivery_status",
+      payload: message,
+    });
+  }
+
+  public async notifyTyping(
+    cell_id: CellId,
+    state: TypingState,
+    agents: AgentPubKey[],
+  ): Promise<void> {
+    const key = encodeCellIdToBase64(cell_id);
+    if (state === "Started") {
+      if (Date.now() - (this.typingSentAt[key] || 0) < TYPING_RATE_LIMIT_MS) return;
+      this.typingSentAt[key] = Date.now();
+    } else {
+      delete this.typingSentAt[key];
+    }
+
+    return this.client.callZome({
+      cell_id,
+      zome_name: ZOME_NAME,
+      fn_name: "notify_typing",
+      payload: { state, agents },
+    });
+  }
+
   public async disableConversationCell(cell_id: CellId) {
     return this.client.disableCloneCell({ clone_cell_id: cell_id[0] });
   }